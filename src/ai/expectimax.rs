@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::ai::clock::now_ms;
+use crate::ai::heuristic::{Heuristic, WeightedHeuristic};
+use crate::domain::bitboard::{BitBoard, BITBOARD_SIZE, MAX_BITBOARD_TILE};
+use crate::domain::board::{Board, Tile};
+use crate::domain::direction::Direction;
+use crate::domain::spawn::SpawnRules;
+
+/// How far and how long a search may run. Depth counts player moves; with a
+/// time limit the search deepens one ply at a time and keeps the last
+/// fully completed answer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchBudget {
+    pub max_depth: u32,
+    pub time_ms: Option<f64>,
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self { max_depth: 3, time_ms: Some(150.0) }
+    }
+}
+
+/// Depth-limited expectimax: max nodes over the four directions, chance
+/// nodes over every spawn the board's rules allow, weighted as they are
+/// drawn: each cell by the spawn policy, each value by its weight, and one
+/// chance level per tile of `per_move`, so more tiles per move cost more.
+/// 4x4 games are searched on a `BitBoard`, other sizes on `Board`; on a
+/// bitboard two 32768 tiles do not merge, which only matters for the last
+/// tiles a game can reach.
+pub struct Expectimax {
+    heuristic: Box<dyn Heuristic>,
+    budget: SearchBudget,
+}
+
+impl Default for Expectimax {
+    fn default() -> Self {
+        Self::new(Box::new(WeightedHeuristic::default()), SearchBudget::default())
+    }
+}
+
+impl Expectimax {
+    pub fn new(heuristic: Box<dyn Heuristic>, budget: SearchBudget) -> Self {
+        Self { heuristic, budget }
+    }
+
+    pub fn budget(&self) -> SearchBudget { self.budget }
+
+    pub fn set_budget(&mut self, budget: SearchBudget) { self.budget = budget; }
+
+    /// Best direction and its expected evaluation, or `None` when no move changes the board.
+    pub fn best_move(&mut self, board: &Board) -> Option<(Direction, f64)> {
+        let deadline = self.budget.time_ms.map(|t| now_ms() + t);
+        let heuristic = self.heuristic.as_ref();
+        match BitBoard::try_from(board) {
+            Ok(bits) if board.spawn.max_value() <= MAX_BITBOARD_TILE => {
+                Search::new(heuristic, &board.spawn, deadline).run(&bits, self.budget.max_depth)
+            }
+            _ => Search::new(heuristic, &board.spawn, deadline).run(board, self.budget.max_depth),
+        }
+    }
+}
+
+/// A position the search can expand.
+trait Position: Sized {
+    type Key: Hash + Eq;
+
+    fn key(&self) -> Self::Key;
+
+    /// The position after sliding `direction`, or `None` when nothing moves.
+    fn after(&self, direction: Direction) -> Option<Self>;
+
+    fn empties(&self) -> Vec<(usize, usize)>;
+
+    fn dims(&self) -> (usize, usize);
+
+    fn with_tile(&self, row: usize, col: usize, value: u32) -> Self;
+
+    fn evaluate(&self, heuristic: &dyn Heuristic) -> f64;
+}
+
+impl Position for Board {
+    type Key = Vec<u32>;
+
+    fn key(&self) -> Vec<u32> { self.cells.iter().map(|c| c.as_ref().map_or(0, |t| t.value)).collect() }
+
+    fn after(&self, direction: Direction) -> Option<Self> {
+        let mut next = self.clone();
+        next.shift(direction).moved.then_some(next)
+    }
+
+    fn empties(&self) -> Vec<(usize, usize)> { self.empty_positions() }
+
+    fn dims(&self) -> (usize, usize) { (self.rows, self.cols) }
+
+    fn with_tile(&self, row: usize, col: usize, value: u32) -> Self {
+        let mut child = self.clone();
+        child.set(row, col, Some(Tile::new(value)));
+        child
+    }
+
+    fn evaluate(&self, heuristic: &dyn Heuristic) -> f64 { heuristic.evaluate(self) }
+}
+
+impl Position for BitBoard {
+    type Key = u64;
+
+    fn key(&self) -> u64 { self.0 }
+
+    fn after(&self, direction: Direction) -> Option<Self> {
+        let (next, _) = self.slide(direction);
+        (next != *self).then_some(next)
+    }
+
+    fn empties(&self) -> Vec<(usize, usize)> { self.empty_positions() }
+
+    fn dims(&self) -> (usize, usize) { (BITBOARD_SIZE, BITBOARD_SIZE) }
+
+    fn with_tile(&self, row: usize, col: usize, value: u32) -> Self {
+        let mut child = *self;
+        child.set(row, col, value.trailing_zeros() as u8);
+        child
+    }
+
+    fn evaluate(&self, heuristic: &dyn Heuristic) -> f64 { heuristic.evaluate_bits(*self) }
+}
+
+/// State of one `best_move` call.
+struct Search<'a, P: Position> {
+    heuristic: &'a dyn Heuristic,
+    rules: &'a SpawnRules,
+    values: Vec<(u32, f64)>,
+    deadline: Option<f64>,
+    cache: HashMap<(P::Key, u32), f64>,
+}
+
+impl<'a, P: Position> Search<'a, P> {
+    fn new(heuristic: &'a dyn Heuristic, rules: &'a SpawnRules, deadline: Option<f64>) -> Self {
+        Self { heuristic, rules, values: rules.probabilities().collect(), deadline, cache: HashMap::new() }
+    }
+
+    /// Deepens one ply at a time up to `max_depth` while time allows.
+    fn run(&mut self, board: &P, max_depth: u32) -> Option<(Direction, f64)> {
+        let mut best = None;
+        for depth in 1..=max_depth.max(1) {
+            self.cache.clear();
+            match self.search_root(board, depth) {
+                Some(found) => best = found,
+                None => break, // out of time; keep the shallower answer
+            }
+            if self.out_of_time() { break; }
+        }
+        best
+    }
+
+    fn out_of_time(&self) -> bool {
+        self.deadline.is_some_and(|d| now_ms() >= d)
+    }
+
+    /// `None` when the deadline hit mid-search, `Some(None)` when no move is legal.
+    fn search_root(&mut self, board: &P, depth: u32) -> Option<Option<(Direction, f64)>> {
+        let mut best: Option<(Direction, f64)> = None;
+        for direction in Direction::ALL {
+            let Some(next) = board.after(direction) else { continue };
+            let value = self.chance(&next, depth - 1, self.rules.per_move)?;
+            if best.is_none_or(|(_, v)| value > v) {
+                best = Some((direction, value));
+            }
+        }
+        // a completed depth-1 search is always usable, even past the deadline
+        if depth > 1 && self.out_of_time() { return None; }
+        Some(best)
+    }
+
+    fn max_node(&mut self, board: &P, depth: u32) -> Option<f64> {
+        if depth == 0 { return Some(board.evaluate(self.heuristic)); }
+        if self.out_of_time() { return None; }
+
+        let key = (board.key(), depth);
+        if let Some(&cached) = self.cache.get(&key) { return Some(cached); }
+
+        let mut best: Option<f64> = None;
+        for direction in Direction::ALL {
+            let Some(next) = board.after(direction) else { continue };
+            let value = self.chance(&next, depth - 1, self.rules.per_move)?;
+            best = Some(best.map_or(value, |b| b.max(value)));
+        }
+        // no legal move: the game ends here, scored as the position stands
+        let value = best.unwrap_or_else(|| board.evaluate(self.heuristic));
+        self.cache.insert(key, value);
+        Some(value)
+    }
+
+    /// Expected value over the `spawns` tiles still to come after a move,
+    /// drawn one at a time like `Board::spawn_tiles`.
+    fn chance(&mut self, board: &P, depth: u32, spawns: usize) -> Option<f64> {
+        let empties = board.empties();
+        if spawns == 0 || empties.is_empty() { return self.max_node(board, depth); }
+
+        let (rows, cols) = board.dims();
+        let cells = self.rules.position_probabilities(&empties, rows, cols);
+        let mut total = 0.0;
+        for (&(row, col), cell_p) in empties.iter().zip(cells) {
+            for i in 0..self.values.len() {
+                let (value, p) = self.values[i];
+                total += cell_p * p * self.chance(&board.with_tile(row, col, value), depth, spawns - 1)?;
+            }
+        }
+        Some(total)
+    }
+}
@@ -0,0 +1,102 @@
+use crate::domain::bitboard::{BitBoard, BITBOARD_SIZE};
+use crate::domain::board::Board;
+use crate::domain::config::GameConfig;
+
+/// Static evaluation of a position; higher is better for the player.
+pub trait Heuristic {
+    fn evaluate(&self, board: &Board) -> f64;
+
+    /// The same evaluation of a 4x4 bitboard, which search uses when it can.
+    /// The default goes through a `Board`; implementations should override it.
+    fn evaluate_bits(&self, board: BitBoard) -> f64 {
+        let mut full = Board::from_config(&GameConfig::new(BITBOARD_SIZE));
+        board.apply_to(&mut full);
+        self.evaluate(&full)
+    }
+}
+
+/// Weighted mix of the usual 2048 features, computed on log2 tile values:
+/// free cells, monotone rows/columns, smoothness between neighbours, merge
+/// opportunities and keeping the largest tile in a corner.
+#[derive(Clone, Debug)]
+pub struct WeightedHeuristic {
+    pub empty: f64,
+    pub monotonicity: f64,
+    pub smoothness: f64,
+    pub merges: f64,
+    pub corner: f64,
+}
+
+impl Default for WeightedHeuristic {
+    fn default() -> Self {
+        Self { empty: 2.7, monotonicity: 1.0, smoothness: 0.1, merges: 0.7, corner: 1.5 }
+    }
+}
+
+fn rank(board: &Board, row: usize, col: usize) -> f64 {
+    board.get(row, col).as_ref().map_or(0.0, |t| (t.value as f64).log2())
+}
+
+/// Penalty for a line that is not sorted in either direction.
+fn line_monotonicity(line: &[f64]) -> f64 {
+    let (mut inc, mut dec) = (0.0, 0.0);
+    for pair in line.windows(2) {
+        if pair[0] > pair[1] { dec += pair[0] - pair[1]; } else { inc += pair[1] - pair[0]; }
+    }
+    -f64::min(inc, dec)
+}
+
+impl Heuristic for WeightedHeuristic {
+    fn evaluate(&self, board: &Board) -> f64 {
+        self.score(board.rows, board.cols, |r, c| rank(board, r, c))
+    }
+
+    fn evaluate_bits(&self, board: BitBoard) -> f64 {
+        // exponents are already log2 values
+        self.score(BITBOARD_SIZE, BITBOARD_SIZE, |r, c| board.get(r, c) as f64)
+    }
+}
+
+impl WeightedHeuristic {
+    /// Scores a `rows` x `cols` grid given the log2 value of each cell (0 when empty).
+    fn score(&self, rows: usize, cols: usize, rank: impl Fn(usize, usize) -> f64) -> f64 {
+        let mut empty = 0.0;
+        let mut smoothness = 0.0;
+        let mut merges = 0.0;
+        let mut max = (0.0, 0, 0);
+        for r in 0..rows {
+            for c in 0..cols {
+                let v = rank(r, c);
+                if v == 0.0 { empty += 1.0; continue; }
+                if v > max.0 { max = (v, r, c); }
+                for (nr, nc) in [(r + 1, c), (r, c + 1)] {
+                    if nr < rows && nc < cols {
+                        let n = rank(nr, nc);
+                        if n == v { merges += 1.0; }
+                        if n != 0.0 { smoothness -= (v - n).abs(); }
+                    }
+                }
+            }
+        }
+
+        let mut monotonicity = 0.0;
+        for r in 0..rows {
+            let line: Vec<f64> = (0..cols).map(|c| rank(r, c)).collect();
+            monotonicity += line_monotonicity(&line);
+        }
+        for c in 0..cols {
+            let line: Vec<f64> = (0..rows).map(|r| rank(r, c)).collect();
+            monotonicity += line_monotonicity(&line);
+        }
+
+        let (_, mr, mc) = max;
+        let in_corner = (mr == 0 || mr == rows - 1) && (mc == 0 || mc == cols - 1);
+        let corner = if in_corner { max.0 } else { 0.0 };
+
+        self.empty * empty
+            + self.monotonicity * monotonicity
+            + self.smoothness * smoothness
+            + self.merges * merges
+            + self.corner * corner
+    }
+}
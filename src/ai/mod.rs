@@ -0,0 +1,7 @@
+pub mod clock;
+pub mod expectimax;
+pub mod heuristic;
+pub mod montecarlo;
+pub mod ntuple;
+pub mod simple;
+pub mod strategy;
@@ -0,0 +1,157 @@
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+use crate::ai::clock::now_ms;
+use crate::ai::strategy::Strategy;
+use crate::domain::bitboard::{BitBoard, MAX_BITBOARD_TILE};
+use crate::domain::board::Board;
+use crate::domain::direction::Direction;
+use crate::domain::rng::GameRng;
+
+/// Without spawns (`per_move == 0`) tiles can slide back and forth forever,
+/// so a rollout gives up after this many moves.
+const MAX_ROLLOUT_MOVES: u32 = 50_000;
+
+/// Rollout moves between two looks at the clock.
+const DEADLINE_CHECK_MOVES: u32 = 64;
+
+fn expired(deadline: Option<f64>) -> bool {
+    deadline.is_some_and(|d| now_ms() >= d)
+}
+
+/// How a rollout picks its moves once the first move is fixed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RolloutPolicy {
+    /// Any direction that changes the board, uniformly.
+    #[default]
+    Random,
+    /// The direction with the largest immediate merge score, random among ties.
+    Greedy,
+}
+
+/// Plays every legal first move followed by `rollouts` games to the end and
+/// picks the move with the best mean final score. Rollouts run one after
+/// another against a wall-clock budget, so no threads are needed, and on
+/// 4x4 boards they play on a `BitBoard`.
+pub struct MonteCarlo {
+    pub rollouts: usize,
+    pub policy: RolloutPolicy,
+    pub time_ms: Option<f64>,
+    rng: GameRng,
+}
+
+impl Default for MonteCarlo {
+    fn default() -> Self {
+        Self::new(100, RolloutPolicy::Random, Some(150.0))
+    }
+}
+
+impl MonteCarlo {
+    pub fn new(rollouts: usize, policy: RolloutPolicy, time_ms: Option<f64>) -> Self {
+        Self { rollouts, policy, time_ms, rng: GameRng::from_entropy() }
+    }
+
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { rng: GameRng::new(seed), ..self }
+    }
+
+    /// Best first move and its mean final score, or `None` when no move is legal.
+    pub fn best_move(&mut self, board: &Board) -> Option<(Direction, f64)> {
+        let first: Vec<Direction> = Direction::ALL.into_iter().filter(|&d| board.clone().shift(d).moved).collect();
+        if first.is_empty() { return None; }
+
+        let bits = BitBoard::try_from(board).ok().filter(|_| board.spawn.max_value() <= MAX_BITBOARD_TILE);
+        let deadline = self.time_ms.map(|t| now_ms() + t);
+        let mut totals = vec![0.0; first.len()];
+        let mut counts = vec![0usize; first.len()];
+        let mut runs = 0;
+        // one round plays each first move once, so an early stop stays fair;
+        // a round the deadline cuts short only counts when it is the first
+        'rounds: while runs < self.rollouts.max(1) {
+            let mut round = Vec::with_capacity(first.len());
+            let mut cut = false;
+            for &direction in &first {
+                let (score, finished) = self.rollout(board, bits, direction, deadline);
+                round.push(score as f64);
+                if !finished {
+                    cut = true;
+                    break;
+                }
+            }
+            if cut && runs > 0 { break 'rounds; }
+            for (i, score) in round.into_iter().enumerate() {
+                totals[i] += score;
+                counts[i] += 1;
+            }
+            runs += 1;
+            if cut || expired(deadline) { break; }
+        }
+
+        first.into_iter()
+            .zip(totals.into_iter().zip(counts))
+            .filter(|&(_, (_, count))| count > 0)
+            .map(|(d, (total, count))| (d, total / count as f64))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Final score of one game that starts with `first`, played on `bits`
+    /// when the board fits one, and whether it finished before `deadline`;
+    /// a cut-short game scores what it had by then.
+    fn rollout(&mut self, board: &Board, bits: Option<BitBoard>, first: Direction, deadline: Option<f64>) -> (u32, bool) {
+        // a fresh stream, so rollouts neither repeat nor peek at the real spawns
+        let rng = GameRng::new(self.rng.next_u64());
+        if let Some(bits) = bits { return self.rollout_bits(bits, board, first, rng, deadline); }
+        let mut game = board.clone();
+        game.rng = rng;
+        game.apply_move(first);
+        for n in 0..MAX_ROLLOUT_MOVES {
+            if n % DEADLINE_CHECK_MOVES == 0 && expired(deadline) { return (game.score, false); }
+            let Some(direction) = self.rollout_move(|d| {
+                let mut next = game.clone();
+                let outcome = next.shift(d);
+                outcome.moved.then_some(outcome.score_gained)
+            }) else { break };
+            game.apply_move(direction);
+        }
+        (game.score, true)
+    }
+
+    /// The same game as `rollout` plays on `board`, spawning from the same stream.
+    fn rollout_bits(&mut self, mut bits: BitBoard, board: &Board, first: Direction, mut rng: GameRng, deadline: Option<f64>) -> (u32, bool) {
+        let mut score = board.score;
+        let mut direction = first;
+        for n in 0..=MAX_ROLLOUT_MOVES {
+            // n == 0 plays `first`, which `rollout` also always plays
+            if n % DEADLINE_CHECK_MOVES == 1 && expired(deadline) { return (score, false); }
+            let (after, gained) = bits.slide(direction);
+            bits = after;
+            score = score.saturating_add(gained);
+            bits.spawn_tiles(&board.spawn, board.spawn.per_move, &mut rng);
+            let Some(next) = self.rollout_move(|d| {
+                let (after, gained) = bits.slide(d);
+                (after != bits).then_some(gained)
+            }) else { break };
+            direction = next;
+        }
+        (score, true)
+    }
+
+    /// Picks among the directions `legal` accepts; it returns the score a
+    /// direction gains, or `None` when it does not move.
+    fn rollout_move(&mut self, legal: impl Fn(Direction) -> Option<u32>) -> Option<Direction> {
+        let mut directions = Direction::ALL;
+        directions.shuffle(&mut self.rng);
+        let legal = directions.into_iter().filter_map(|d| legal(d).map(|gained| (d, gained)));
+        match self.policy {
+            RolloutPolicy::Random => legal.map(|(d, _)| d).next(),
+            // the shuffle above breaks ties; max_by_key keeps the last maximum
+            RolloutPolicy::Greedy => legal.max_by_key(|&(_, gained)| gained).map(|(d, _)| d),
+        }
+    }
+}
+
+impl Strategy for MonteCarlo {
+    fn name(&self) -> &str { "montecarlo" }
+
+    fn choose(&mut self, board: &Board) -> Option<(Direction, f64)> { self.best_move(board) }
+}
@@ -0,0 +1,238 @@
+use crate::ai::strategy::Strategy;
+use crate::domain::bitboard::{BitBoard, BITBOARD_SIZE};
+use crate::domain::board::Board;
+use crate::domain::direction::Direction;
+
+const CELLS: usize = BITBOARD_SIZE * BITBOARD_SIZE;
+const MAX_TUPLE_LEN: usize = 6;
+const WEIGHTS_MAGIC: &[u8; 4] = b"NTUP";
+const WEIGHTS_VERSION: u32 = 1;
+
+/// Four-cell rows and squares; with the eight symmetries they cover every
+/// line and 2x2 block of the board while keeping 65536 weights per tuple.
+pub const DEFAULT_TUPLES: [[usize; 4]; 5] = [
+    [0, 1, 2, 3],
+    [4, 5, 6, 7],
+    [0, 1, 4, 5],
+    [1, 2, 5, 6],
+    [5, 6, 9, 10],
+];
+
+/// Where cell `row * 4 + col` lands under each of the 8 rotations and
+/// reflections of the board.
+fn symmetries() -> [[usize; CELLS]; 8] {
+    let last = BITBOARD_SIZE - 1;
+    let mut out = [[0; CELLS]; 8];
+    for (s, map) in out.iter_mut().enumerate() {
+        for (cell, target) in map.iter_mut().enumerate() {
+            let (mut r, mut c) = (cell / BITBOARD_SIZE, cell % BITBOARD_SIZE);
+            for _ in 0..s % 4 {
+                (r, c) = (c, last - r);
+            }
+            if s >= 4 { c = last - c; }
+            *target = r * BITBOARD_SIZE + c;
+        }
+    }
+    out
+}
+
+/// An n-tuple network over 4x4 bitboards: each tuple of cells indexes a
+/// weight table by the exponents it sees, and the value of a position is
+/// the sum over all tuples in all 8 symmetric placements, which share one
+/// table per tuple.
+///
+/// Values estimate the score still to come from an afterstate (the board
+/// right after a slide, before the spawn), as learned by [`train_episode`].
+///
+/// [`train_episode`]: NTupleNetwork::train_episode
+#[derive(Clone, Debug)]
+pub struct NTupleNetwork {
+    tuples: Vec<Vec<usize>>,
+    /// `placements[t][s]` is tuple `t` mapped through symmetry `s`.
+    placements: Vec<Vec<Vec<usize>>>,
+    weights: Vec<Vec<f32>>,
+}
+
+impl Default for NTupleNetwork {
+    fn default() -> Self {
+        let tuples = DEFAULT_TUPLES.iter().map(|t| t.to_vec()).collect();
+        Self::new(tuples).expect("default tuples are valid")
+    }
+}
+
+impl NTupleNetwork {
+    /// A zero-initialised network; tuples are lists of cell indices `row * 4 + col`.
+    pub fn new(tuples: Vec<Vec<usize>>) -> Result<Self, String> {
+        if tuples.is_empty() {
+            return Err("N-tuple agi bos olamaz".to_string());
+        }
+        for tuple in &tuples {
+            if tuple.is_empty() || tuple.len() > MAX_TUPLE_LEN {
+                return Err(format!("Tuple uzunlugu 1 ile {} arasinda olmali: {:?}", MAX_TUPLE_LEN, tuple));
+            }
+            if tuple.iter().any(|&cell| cell >= CELLS) {
+                return Err(format!("Tuple'da gecersiz hucre: {:?}", tuple));
+            }
+        }
+        let symmetries = symmetries();
+        let placements = tuples.iter()
+            .map(|t| symmetries.iter().map(|map| t.iter().map(|&cell| map[cell]).collect()).collect())
+            .collect();
+        let weights = tuples.iter().map(|t| vec![0.0; 1 << (4 * t.len())]).collect();
+        Ok(Self { tuples, placements, weights })
+    }
+
+    pub fn tuples(&self) -> &[Vec<usize>] { &self.tuples }
+
+    fn index(board: BitBoard, cells: &[usize]) -> usize {
+        cells.iter().fold(0, |acc, &cell| (acc << 4) | board.get(cell / BITBOARD_SIZE, cell % BITBOARD_SIZE) as usize)
+    }
+
+    pub fn evaluate(&self, board: BitBoard) -> f64 {
+        let mut total = 0.0;
+        for (placements, weights) in self.placements.iter().zip(&self.weights) {
+            for cells in placements {
+                total += weights[Self::index(board, cells)] as f64;
+            }
+        }
+        total
+    }
+
+    /// Moves the value of `board` by `delta`, split evenly over every weight it reads.
+    fn update(&mut self, board: BitBoard, delta: f64) {
+        let features = (self.tuples.len() * 8) as f64;
+        let step = (delta / features) as f32;
+        for (placements, weights) in self.placements.iter().zip(self.weights.iter_mut()) {
+            for cells in placements {
+                weights[Self::index(board, cells)] += step;
+            }
+        }
+    }
+
+    /// The move maximising merge score plus afterstate value, with that total.
+    pub fn best_move(&self, board: BitBoard) -> Option<(Direction, f64)> {
+        self.best_afterstate(board).map(|(d, _, _, value)| (d, value))
+    }
+
+    /// Direction, afterstate, merge score and total value of the best move.
+    fn best_afterstate(&self, board: BitBoard) -> Option<(Direction, BitBoard, u32, f64)> {
+        Direction::ALL.into_iter()
+            .filter_map(|d| {
+                let (after, gained) = board.slide(d);
+                (after != board).then(|| (d, after, gained, gained as f64 + self.evaluate(after)))
+            })
+            .max_by(|a, b| a.3.total_cmp(&b.3))
+    }
+
+    /// Plays `board` (4x4) to the end with the current network, learning
+    /// from each step by TD(0) on afterstates, and returns the final board.
+    /// A tile too large for the bitboard ends the episode like a lost game.
+    pub fn train_episode(&mut self, mut board: Board, alpha: f64) -> Result<Board, String> {
+        self.supports(board.rows, board.cols)?;
+        let mut previous: Option<BitBoard> = None;
+        while let Ok(state) = BitBoard::try_from(&board)
+            && let Some((direction, after, gained, _)) = self.best_afterstate(state) {
+            if let Some(prev) = previous {
+                let error = gained as f64 + self.evaluate(after) - self.evaluate(prev);
+                self.update(prev, alpha * error);
+            }
+            previous = Some(after);
+
+            board.shift(direction);
+            board.spawn_tiles(board.spawn.per_move);
+        }
+        if let Some(prev) = previous {
+            // nothing follows a terminal afterstate
+            let error = -self.evaluate(prev);
+            self.update(prev, alpha * error);
+        }
+        Ok(board)
+    }
+
+    /// `b"NTUP"`, format version (`u32` LE), tuple count, each tuple as its
+    /// length and cell indices (`u32` LE), then every tuple's weights as `f32` LE.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = WEIGHTS_MAGIC.to_vec();
+        out.extend_from_slice(&WEIGHTS_VERSION.to_le_bytes());
+        out.extend_from_slice(&(self.tuples.len() as u32).to_le_bytes());
+        for tuple in &self.tuples {
+            out.extend_from_slice(&(tuple.len() as u32).to_le_bytes());
+            for &cell in tuple {
+                out.extend_from_slice(&(cell as u32).to_le_bytes());
+            }
+        }
+        for weights in &self.weights {
+            for w in weights {
+                out.extend_from_slice(&w.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader(bytes);
+        if reader.take(4)? != WEIGHTS_MAGIC {
+            return Err("Gecersiz agirlik dosyasi basligi".to_string());
+        }
+        let version = reader.u32()?;
+        if version != WEIGHTS_VERSION {
+            return Err(format!("Agirlik dosyasi surumu desteklenmiyor: {}", version));
+        }
+        let count = reader.u32()? as usize;
+        let mut tuples = Vec::new();
+        for _ in 0..count {
+            let len = reader.u32()? as usize;
+            if len > MAX_TUPLE_LEN {
+                return Err(format!("Tuple uzunlugu 1 ile {} arasinda olmali: {}", MAX_TUPLE_LEN, len));
+            }
+            tuples.push((0..len).map(|_| reader.u32().map(|c| c as usize)).collect::<Result<Vec<_>, _>>()?);
+        }
+        // check the size before allocating: a short file can declare gigabytes of tables
+        let expected = tuples.iter()
+            .try_fold(0usize, |total, t| 4usize.checked_shl(4 * t.len() as u32).and_then(|n| total.checked_add(n)));
+        if expected != Some(reader.0.len()) {
+            return Err("Agirlik dosyasinin boyutu tuple'larla tutmuyor".to_string());
+        }
+        let mut network = Self::new(tuples)?;
+        for weights in network.weights.iter_mut() {
+            for w in weights.iter_mut() {
+                *w = f32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+                if !w.is_finite() {
+                    return Err("Agirlik dosyasinda sonlu olmayan agirlik var".to_string());
+                }
+            }
+        }
+        Ok(network)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.0.len() < n {
+            return Err("Agirlik dosyasi eksik".to_string());
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+impl Strategy for NTupleNetwork {
+    fn name(&self) -> &str { "ntuple" }
+
+    /// A board with a tile the bitboard cannot hold gets no suggestion.
+    fn choose(&mut self, board: &Board) -> Option<(Direction, f64)> {
+        self.best_move(BitBoard::try_from(board).ok()?)
+    }
+
+    fn supports(&self, rows: usize, cols: usize) -> Result<(), String> {
+        if rows == BITBOARD_SIZE && cols == BITBOARD_SIZE { return Ok(()); }
+        Err(format!("N-tuple agi yalnizca 4x4 tahtada oynar: {}x{}", rows, cols))
+    }
+}
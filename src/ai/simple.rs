@@ -0,0 +1,65 @@
+use rand::seq::SliceRandom;
+
+use crate::ai::strategy::Strategy;
+use crate::domain::board::Board;
+use crate::domain::direction::Direction;
+use crate::domain::rng::GameRng;
+
+/// Legal directions with the merge score each would gain, in `order`.
+fn legal_moves(board: &Board, order: [Direction; 4]) -> impl Iterator<Item = (Direction, u32)> + '_ {
+    order.into_iter().filter_map(|d| {
+        let outcome = board.clone().shift(d);
+        outcome.moved.then_some((d, outcome.score_gained))
+    })
+}
+
+/// Any legal direction, uniformly; the baseline every other player should beat.
+pub struct RandomPlayer {
+    rng: GameRng,
+}
+
+impl RandomPlayer {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: GameRng::new(seed) }
+    }
+}
+
+impl Strategy for RandomPlayer {
+    fn name(&self) -> &str { "random" }
+
+    fn choose(&mut self, board: &Board) -> Option<(Direction, f64)> {
+        let mut order = Direction::ALL;
+        order.shuffle(&mut self.rng);
+        legal_moves(board, order).next().map(|(d, _)| (d, 0.0))
+    }
+}
+
+/// The direction with the largest immediate merge score; earlier directions
+/// in `Direction::ALL` win ties.
+pub struct GreedyPlayer;
+
+impl Strategy for GreedyPlayer {
+    fn name(&self) -> &str { "greedy" }
+
+    fn choose(&mut self, board: &Board) -> Option<(Direction, f64)> {
+        // max_by_key keeps the last maximum, so walk the directions backwards
+        let mut order = Direction::ALL;
+        order.reverse();
+        legal_moves(board, order)
+            .max_by_key(|&(_, gained)| gained)
+            .map(|(d, gained)| (d, gained as f64))
+    }
+}
+
+/// The classic corner habit: down, then left, then right, and up only when
+/// nothing else moves, which keeps big tiles in the bottom-left corner.
+pub struct CornerPlayer;
+
+impl Strategy for CornerPlayer {
+    fn name(&self) -> &str { "corner" }
+
+    fn choose(&mut self, board: &Board) -> Option<(Direction, f64)> {
+        let order = [Direction::Down, Direction::Left, Direction::Right, Direction::Up];
+        legal_moves(board, order).next().map(|(d, gained)| (d, gained as f64))
+    }
+}
@@ -0,0 +1,63 @@
+use crate::ai::expectimax::{Expectimax, SearchBudget};
+use crate::ai::heuristic::WeightedHeuristic;
+use crate::ai::montecarlo::{MonteCarlo, RolloutPolicy};
+use crate::ai::ntuple::NTupleNetwork;
+use crate::ai::simple::{CornerPlayer, GreedyPlayer, RandomPlayer};
+use crate::domain::board::Board;
+use crate::domain::direction::Direction;
+
+/// Anything that can pick a move: search, rollouts, learned evaluators or
+/// simple rules. The score is the strategy's own estimate and only compares
+/// moves within one strategy.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    /// `None` when no direction changes the board.
+    fn choose(&mut self, board: &Board) -> Option<(Direction, f64)>;
+
+    /// Errs for board sizes it cannot play; most strategies play any size.
+    fn supports(&self, _rows: usize, _cols: usize) -> Result<(), String> { Ok(()) }
+}
+
+impl Strategy for Expectimax {
+    fn name(&self) -> &str { "expectimax" }
+
+    fn choose(&mut self, board: &Board) -> Option<(Direction, f64)> { self.best_move(board) }
+}
+
+/// Every name `strategy_by_name` accepts.
+pub const STRATEGY_NAMES: [&str; 6] = ["random", "greedy", "corner", "expectimax", "montecarlo", "ntuple"];
+
+/// What the named strategies are built from; each reads only its own fields.
+#[derive(Clone, Debug)]
+pub struct StrategyOptions {
+    /// Seeds the random player and the Monte Carlo rollouts.
+    pub seed: u64,
+    /// Expectimax depth, searched without a time limit so runs are reproducible.
+    pub depth: u32,
+    pub rollouts: usize,
+    /// Weights for `ntuple`, which has no useful untrained default.
+    pub ntuple: Option<NTupleNetwork>,
+}
+
+impl Default for StrategyOptions {
+    fn default() -> Self {
+        Self { seed: 0, depth: 2, rollouts: 50, ntuple: None }
+    }
+}
+
+/// Builds the strategy called `name`, as the command-line tools select them.
+pub fn strategy_by_name(name: &str, options: &StrategyOptions) -> Result<Box<dyn Strategy>, String> {
+    Ok(match name {
+        "random" => Box::new(RandomPlayer::new(options.seed)),
+        "greedy" => Box::new(GreedyPlayer),
+        "corner" => Box::new(CornerPlayer),
+        "expectimax" => {
+            let budget = SearchBudget { max_depth: options.depth, time_ms: None };
+            Box::new(Expectimax::new(Box::new(WeightedHeuristic::default()), budget))
+        }
+        "montecarlo" => Box::new(MonteCarlo::new(options.rollouts, RolloutPolicy::Random, None).with_seed(options.seed)),
+        "ntuple" => Box::new(options.ntuple.clone().ok_or("ntuple icin agirlik dosyasi gerekli")?),
+        other => return Err(format!("Bilinmeyen strateji: {} ({})", other, STRATEGY_NAMES.join(", "))),
+    })
+}
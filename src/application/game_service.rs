@@ -1,34 +1,226 @@
-use crate::domain::board::{Board, MoveEvent};
+use crate::ai::clock::now_ms;
+use crate::ai::expectimax::{Expectimax, SearchBudget};
+use crate::ai::heuristic::WeightedHeuristic;
+use crate::ai::strategy::Strategy;
+use crate::application::history::{History, DEFAULT_UNDO_DEPTH};
+use crate::application::recording::Recording;
+use crate::application::replay::{Replay, ReplayAction, ReplayLog};
+use crate::application::snapshot::GameSnapshot;
+use crate::domain::board::{Board, MoveOutcome};
+use crate::domain::config::GameConfig;
 use crate::domain::direction::Direction;
+use crate::domain::rng::GameRng;
 
 pub struct GameService {
+    config: GameConfig,
     board: Board,
+    history: History,
+    moves: u32,
+    best_score: u32,
+    keep_playing: bool,
+    /// Player behind `best_move`; a default expectimax search when unset.
+    strategy: Option<Box<dyn Strategy>>,
+    recording: Option<Recording>,
+    /// Every action since the game started; `None` for a game restored from
+    /// a save that predates replays.
+    replay: Option<ReplayLog>,
 }
 
 impl GameService {
     pub fn new(size: usize) -> Self {
-        Self { board: Board::new(size) }
+        Self::with_config(GameConfig::new(size))
     }
 
+    pub fn with_seed(size: usize, seed: u64) -> Self {
+        Self::with_config(GameConfig { seed: Some(seed), ..GameConfig::new(size) })
+    }
+
+    pub fn with_config(config: GameConfig) -> Self {
+        let seed = config.seed.unwrap_or_else(rand::random);
+        let board = Board::with_rng(&config, GameRng::new(seed));
+        Self {
+            config,
+            board,
+            history: History::new(Some(DEFAULT_UNDO_DEPTH)),
+            moves: 0,
+            best_score: 0,
+            keep_playing: false,
+            strategy: None,
+            recording: None,
+            replay: Some(ReplayLog::new(seed, now_ms())),
+        }
+    }
+
+    pub fn from_snapshot(snapshot: GameSnapshot) -> Self {
+        let best_score = snapshot.board.score;
+        Self {
+            config: snapshot.config,
+            board: snapshot.board,
+            history: snapshot.history,
+            moves: snapshot.moves,
+            best_score,
+            keep_playing: snapshot.keep_playing,
+            strategy: None,
+            recording: None,
+            replay: snapshot.replay,
+        }
+    }
+
+    pub fn snapshot(&self) -> GameSnapshot {
+        let snapshot = GameSnapshot::new(self.config.clone(), self.board.clone(), self.moves, self.history.clone(), self.keep_playing);
+        GameSnapshot { replay: self.replay.clone(), ..snapshot }
+    }
+
+    pub fn config(&self) -> &GameConfig { &self.config }
+
     pub fn board(&self) -> &Board { &self.board }
 
     pub fn is_over(&self) -> bool { !self.board.can_move() }
 
     pub fn is_won(&self) -> bool { self.board.is_won() }
 
+    /// The target was reached and the player has not chosen to continue yet;
+    /// moves are refused until `keep_playing` or `reset`.
+    pub fn win_pending(&self) -> bool { self.is_won() && !self.keep_playing }
+
+    pub fn keep_playing(&mut self) {
+        if !self.keep_playing { self.log(ReplayAction::KeepPlaying); }
+        self.keep_playing = true;
+    }
+
+    pub fn is_keep_playing(&self) -> bool { self.keep_playing }
+
     pub fn score(&self) -> u32 { self.board.score }
 
+    pub fn moves(&self) -> u32 { self.moves }
+
+    pub fn best_score(&self) -> u32 { self.best_score }
+
+    /// Seeds the best score from an earlier session; it only ever grows.
+    pub fn set_best_score(&mut self, best: u32) { self.best_score = self.best_score.max(best); }
+
     pub fn reset(&mut self) {
-        let size = self.board.size;
-        self.board = Board::new(size);
+        // keep drawing from the same stream so seeded games stay reproducible
+        let rng = self.board.rng.clone();
+        self.replay = Some(ReplayLog::new(rng.state(), now_ms()));
+        self.board = Board::with_rng(&self.config, rng);
+        self.history.clear();
+        self.moves = 0;
+        self.keep_playing = false;
+        self.record_cut();
+    }
+
+    /// Starts over under `config`, keeping best score, undo depth and solver.
+    /// A running recording stops, since its frames share one board size, and
+    /// a strategy that cannot play the new size gives way to expectimax.
+    pub fn restart_with(&mut self, config: GameConfig) {
+        if self.strategy.as_ref().is_some_and(|s| s.supports(config.rows, config.cols).is_err()) { self.strategy = None; }
+        let seed = config.seed.unwrap_or_else(rand::random);
+        self.board = Board::with_rng(&config, GameRng::new(seed));
+        self.config = config;
+        self.replay = Some(ReplayLog::new(seed, now_ms()));
+        self.history.clear();
+        self.moves = 0;
+        self.keep_playing = false;
+        self.recording = None;
+    }
+
+    pub fn apply_move(&mut self, dir: Direction) -> MoveOutcome {
+        if self.win_pending() { return MoveOutcome::default(); }
+        let before = self.board.clone();
+        let outcome = self.board.apply_move(dir);
+        if outcome.moved {
+            self.record(before);
+            self.log(ReplayAction::Move(dir));
+            if let Some(recording) = self.recording.as_mut() { recording.push_move(dir, outcome.clone(), &self.board); }
+        }
+        outcome
     }
 
     pub fn slide(&mut self, dir: Direction) -> bool {
-        self.board.slide(dir)
+        self.apply_move(dir).moved
     }
 
-    pub fn slide_with_events(&mut self, dir: Direction) -> (bool, Vec<MoveEvent>) {
-        self.board.slide_with_animations(dir)
+    /// Suggested move and the strategy's score for it.
+    pub fn best_move(&mut self) -> Option<(Direction, f64)> {
+        self.best_move_within(SearchBudget::default())
+    }
+
+    /// Like `best_move`, but the default expectimax search stays within
+    /// `budget`; a strategy from `set_strategy` keeps its own limits.
+    pub fn best_move_within(&mut self, budget: SearchBudget) -> Option<(Direction, f64)> {
+        if self.win_pending() { return None; }
+        match self.strategy.as_mut() {
+            Some(strategy) => strategy.choose(&self.board),
+            None => Expectimax::new(Box::new(WeightedHeuristic::default()), budget).best_move(&self.board),
+        }
     }
-}
 
+    /// Replaces the player behind `best_move`; `None` goes back to expectimax.
+    /// A strategy that cannot play this board size is refused.
+    pub fn set_strategy(&mut self, strategy: Option<Box<dyn Strategy>>) -> Result<(), String> {
+        if let Some(strategy) = &strategy { strategy.supports(self.config.rows, self.config.cols)?; }
+        self.strategy = strategy;
+        Ok(())
+    }
+
+    pub fn strategy_name(&self) -> &str { self.strategy.as_ref().map_or("expectimax", |s| s.name()) }
+
+    fn record(&mut self, before: Board) {
+        self.history.record(before);
+        self.moves += 1;
+        self.best_score = self.best_score.max(self.board.score);
+    }
+
+    /// `None` keeps every move, `Some(0)` turns undo off.
+    pub fn set_undo_depth(&mut self, depth: Option<usize>) { self.history.set_limit(depth); }
+
+    pub fn undo_depth(&self) -> Option<usize> { self.history.limit() }
+
+    pub fn can_undo(&self) -> bool { self.history.can_undo() }
+
+    pub fn can_redo(&self) -> bool { self.history.can_redo() }
+
+    pub fn undo(&mut self) -> bool {
+        let undone = self.history.undo(&mut self.board);
+        if undone {
+            self.moves = self.moves.saturating_sub(1);
+            self.log(ReplayAction::Undo);
+            self.record_cut();
+        }
+        undone
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let redone = self.history.redo(&mut self.board);
+        if redone {
+            self.moves += 1;
+            self.log(ReplayAction::Redo);
+            self.record_cut();
+        }
+        redone
+    }
+
+    /// Starts recording every move from the current board, dropping any earlier recording.
+    pub fn start_recording(&mut self) { self.recording = Some(Recording::new(self.board.clone())); }
+
+    pub fn stop_recording(&mut self) -> Option<Recording> { self.recording.take() }
+
+    pub fn recording(&self) -> Option<&Recording> { self.recording.as_ref() }
+
+    fn record_cut(&mut self) {
+        if let Some(recording) = self.recording.as_mut() { recording.push_cut(&self.board); }
+    }
+
+    /// The game so far as a replay file, or `None` if it was restored from a
+    /// save without one.
+    pub fn replay(&self) -> Option<Replay> {
+        let log = self.replay.as_ref()?;
+        Some(Replay::new(log.seed, self.config.clone(), log.steps.clone(), &self.board, self.moves))
+    }
+
+    /// A game that outgrows the log loses its replay rather than keep a partial one.
+    fn log(&mut self, action: ReplayAction) {
+        if let Some(log) = self.replay.as_mut() && !log.push(action, now_ms()) { self.replay = None; }
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::board::Board;
+
+pub const DEFAULT_UNDO_DEPTH: usize = 64;
+
+/// Undo/redo stacks of whole boards. A board carries its score and RNG state,
+/// so restoring one also restores the tile that will spawn next.
+///
+/// `limit` is the number of undo steps kept: `None` is unlimited and
+/// `Some(0)` disables undo entirely (e.g. for ranked play).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct History {
+    limit: Option<usize>,
+    undo: VecDeque<Board>,
+    redo: Vec<Board>,
+}
+
+impl History {
+    pub fn new(limit: Option<usize>) -> Self {
+        Self { limit, undo: VecDeque::new(), redo: Vec::new() }
+    }
+
+    pub(crate) fn from_parts(limit: Option<usize>, undo: VecDeque<Board>, redo: Vec<Board>) -> Self {
+        let mut history = Self { limit, undo, redo };
+        history.trim();
+        history
+    }
+
+    pub fn limit(&self) -> Option<usize> { self.limit }
+
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        self.trim();
+        if limit == Some(0) { self.redo.clear(); }
+    }
+
+    /// Every stored board, undo and redo alike.
+    pub(crate) fn boards(&self) -> impl Iterator<Item = &Board> {
+        self.undo.iter().chain(&self.redo)
+    }
+
+    pub fn can_undo(&self) -> bool { !self.undo.is_empty() }
+
+    pub fn can_redo(&self) -> bool { !self.redo.is_empty() }
+
+    /// Remembers the board as it was before a move; a new move invalidates redo.
+    pub fn record(&mut self, before: Board) {
+        self.redo.clear();
+        self.push_undo(before);
+    }
+
+    pub fn undo(&mut self, current: &mut Board) -> bool {
+        match self.undo.pop_back() {
+            Some(previous) => {
+                self.redo.push(std::mem::replace(current, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self, current: &mut Board) -> bool {
+        match self.redo.pop() {
+            Some(next) => {
+                let previous = std::mem::replace(current, next);
+                self.push_undo(previous);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    fn push_undo(&mut self, board: Board) {
+        if self.limit == Some(0) { return; }
+        self.undo.push_back(board);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        if let Some(limit) = self.limit {
+            while self.undo.len() > limit {
+                self.undo.pop_front();
+            }
+        }
+    }
+}
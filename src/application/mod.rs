@@ -1 +1,5 @@
-pub mod game_service;
\ No newline at end of file
+pub mod game_service;
+pub mod history;
+pub mod recording;
+pub mod replay;
+pub mod snapshot;
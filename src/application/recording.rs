@@ -0,0 +1,37 @@
+use crate::domain::board::{Board, MoveOutcome};
+use crate::domain::direction::Direction;
+
+/// One change of the board while recording. `direction` is `None` when the
+/// board jumped without a move (undo, redo, reset), which plays back as a cut.
+#[derive(Clone, Debug)]
+pub struct RecordedStep {
+    pub direction: Option<Direction>,
+    pub outcome: MoveOutcome,
+    /// The board after the step.
+    pub board: Board,
+}
+
+/// A game as it was played, for exporting it as an animation.
+#[derive(Clone, Debug)]
+pub struct Recording {
+    pub initial: Board,
+    pub steps: Vec<RecordedStep>,
+}
+
+impl Recording {
+    pub fn new(initial: Board) -> Self {
+        Self { initial, steps: Vec::new() }
+    }
+
+    pub fn push_move(&mut self, direction: Direction, outcome: MoveOutcome, board: &Board) {
+        self.steps.push(RecordedStep { direction: Some(direction), outcome, board: board.clone() });
+    }
+
+    pub fn push_cut(&mut self, board: &Board) {
+        self.steps.push(RecordedStep { direction: None, outcome: MoveOutcome::default(), board: board.clone() });
+    }
+
+    pub fn final_board(&self) -> &Board {
+        self.steps.last().map_or(&self.initial, |s| &s.board)
+    }
+}
@@ -0,0 +1,318 @@
+//! Replay files: the seed and rules a game started from, every action in
+//! order, and the result, so the game can be re-simulated exactly and
+//! checked against what was stored.
+//!
+//! A replay is a JSON document (version 1):
+//!
+//! ```text
+//! {
+//!   "version": 1,
+//!   "seed": 42,                      // state of the tile RNG before the first spawn
+//!   "config": { "rows": 4, "cols": 4, "win_target": 2048, "seed": null,
+//!               "spawn": { "values": [[2, 9], [4, 1]], "per_move": 1, "initial": 2, "policy": "Uniform" } },
+//!   "actions": [{ "action": "left", "t_ms": 812 }, { "action": "undo", "t_ms": 1400 }, ...],
+//!   "final_score": 1234,
+//!   "final_moves": 160,
+//!   "final_board": [0, 2, 4, 0, ...],  // tile values row by row, 0 for empty
+//!   "checksum": "9c1f0e6d2a4b7e13"
+//! }
+//! ```
+//!
+//! `config` carries the board size and the ruleset (win target and spawn
+//! rules); its own `seed` is ignored in favour of the top-level one.
+//! Actions are `up`, `down`, `left`, `right`, `undo`, `redo` and `continue`
+//! (keep playing after a win); `t_ms` counts from the start of the game.
+//! `checksum` is the FNV-1a hash, as 16 hex digits, of the compact JSON of
+//! every other field in the order above.
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::game_service::GameService;
+use crate::domain::board::{Board, MoveOutcome};
+use crate::domain::config::GameConfig;
+use crate::domain::direction::Direction;
+
+/// Bump whenever the replay format changes shape.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Actions a game keeps for its replay. The log is saved with every move,
+/// so a longer game drops it rather than outgrow the browser's storage.
+pub const MAX_REPLAY_STEPS: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum ReplayAction {
+    Move(Direction),
+    Undo,
+    Redo,
+    KeepPlaying,
+}
+
+impl ReplayAction {
+    pub const ALL: [ReplayAction; 7] = [
+        ReplayAction::Move(Direction::Up),
+        ReplayAction::Move(Direction::Down),
+        ReplayAction::Move(Direction::Left),
+        ReplayAction::Move(Direction::Right),
+        ReplayAction::Undo,
+        ReplayAction::Redo,
+        ReplayAction::KeepPlaying,
+    ];
+
+    /// Also the name of the matching `InputAction`, which borrows it.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReplayAction::Move(direction) => direction.as_str(),
+            ReplayAction::Undo => "undo",
+            ReplayAction::Redo => "redo",
+            ReplayAction::KeepPlaying => "continue",
+        }
+    }
+}
+
+/// The entry of `all` that `name` calls `s`; shared by every action type
+/// that is named by string.
+pub(crate) fn parse_named<T: Copy>(all: &[T], name: fn(T) -> &'static str, s: &str) -> Result<T, String> {
+    all.iter().copied().find(|&a| name(a) == s).ok_or_else(|| format!("Bilinmeyen eylem: {}", s))
+}
+
+impl From<ReplayAction> for String {
+    fn from(action: ReplayAction) -> Self { action.as_str().to_string() }
+}
+
+impl TryFrom<String> for ReplayAction {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::str::FromStr for ReplayAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_named(&ReplayAction::ALL, ReplayAction::as_str, s)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub action: ReplayAction,
+    /// Whole milliseconds since the game started.
+    pub t_ms: u64,
+}
+
+/// The actions of the game in progress, kept by `GameService` and saved
+/// with its snapshot so a reloaded game keeps its replay.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayLog {
+    pub seed: u64,
+    pub steps: Vec<ReplayStep>,
+    /// Clock reading at `t_ms == 0`; after a reload the clock resumes from the
+    /// last step, so time spent away is not counted.
+    #[serde(skip)]
+    origin_ms: Option<f64>,
+}
+
+impl ReplayLog {
+    pub fn new(seed: u64, now_ms: f64) -> Self {
+        Self { seed, steps: Vec::new(), origin_ms: Some(now_ms) }
+    }
+
+    /// Appends `action`; false, with nothing stored, once the log holds
+    /// `MAX_REPLAY_STEPS` actions.
+    pub fn push(&mut self, action: ReplayAction, now_ms: f64) -> bool {
+        if self.steps.len() >= MAX_REPLAY_STEPS { return false; }
+        let last = self.steps.last().map_or(0, |s| s.t_ms);
+        let origin = *self.origin_ms.get_or_insert(now_ms - last as f64);
+        self.steps.push(ReplayStep { action, t_ms: ((now_ms - origin).round() as u64).max(last) });
+        true
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub config: GameConfig,
+    pub actions: Vec<ReplayStep>,
+    pub final_score: u32,
+    pub final_moves: u32,
+    pub final_board: Vec<u32>,
+    pub checksum: String,
+}
+
+fn tile_values(board: &Board) -> Vec<u32> {
+    board.cells.iter().map(|cell| cell.as_ref().map_or(0, |t| t.value)).collect()
+}
+
+/// 64-bit FNV-1a.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+impl Replay {
+    /// The replay of a game started from `seed` whose actions led to `board`
+    /// after `final_moves` moves.
+    pub fn new(seed: u64, config: GameConfig, actions: Vec<ReplayStep>, board: &Board, final_moves: u32) -> Self {
+        let config = GameConfig { seed: None, ..config };
+        let mut replay = Self {
+            version: REPLAY_VERSION,
+            seed,
+            config,
+            actions,
+            final_score: board.score,
+            final_moves,
+            final_board: tile_values(board),
+            checksum: String::new(),
+        };
+        replay.checksum = replay.compute_checksum();
+        replay
+    }
+
+    pub fn compute_checksum(&self) -> String {
+        let body = (self.version, self.seed, &self.config, &self.actions, self.final_score, self.final_moves, &self.final_board);
+        let json = serde_json::to_string(&body).unwrap_or_default();
+        format!("{:016x}", fnv1a(json.as_bytes()))
+    }
+
+    /// The configuration to re-simulate with: the stored rules and the seed.
+    pub fn game_config(&self) -> GameConfig {
+        GameConfig { seed: Some(self.seed), ..self.config.clone() }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+
+    /// Parses a replay and checks its version and checksum; `ReplayPlayer`
+    /// checks that it actually plays out.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let replay: Replay = serde_json::from_str(json).map_err(|e| format!("Tekrar dosyasi okunamadi: {}", e))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(format!("Tekrar surumu desteklenmiyor: {}", replay.version));
+        }
+        if replay.checksum != replay.compute_checksum() {
+            return Err("Tekrar dosyasi bozuk: saglama toplami tutmuyor".to_string());
+        }
+        Ok(replay)
+    }
+}
+
+/// The game after each action of a replay.
+#[derive(Clone, Debug)]
+pub struct ReplayFrame {
+    /// `None` for the starting position.
+    pub step: Option<ReplayStep>,
+    /// What the action moved, for animating it; empty for anything but a move.
+    pub outcome: MoveOutcome,
+    pub board: Board,
+    pub moves: u32,
+    pub best_score: u32,
+    pub win_pending: bool,
+}
+
+impl ReplayFrame {
+    fn capture(game: &GameService, step: Option<ReplayStep>, outcome: MoveOutcome) -> Self {
+        Self {
+            step,
+            outcome,
+            board: game.board().clone(),
+            moves: game.moves(),
+            best_score: game.best_score(),
+            win_pending: game.win_pending(),
+        }
+    }
+}
+
+/// Re-simulates a replay up front, refusing one that does not play out to
+/// its stored result, then steps through it in either direction.
+#[derive(Clone, Debug)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    frames: Vec<ReplayFrame>,
+    position: usize,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Result<Self, String> {
+        // every action keeps a frame, so a file has the same cap as a live game
+        if replay.actions.len() > MAX_REPLAY_STEPS {
+            return Err(format!("Tekrar cok uzun: {} eylem, en fazla {}", replay.actions.len(), MAX_REPLAY_STEPS));
+        }
+        let config = replay.game_config();
+        config.validate()?;
+        let mut game = GameService::with_config(config);
+        // the recorded undos were possible with whatever depth was set, so they are with no limit
+        game.set_undo_depth(None);
+
+        let mut frames = vec![ReplayFrame::capture(&game, None, MoveOutcome::default())];
+        for (i, step) in replay.actions.iter().enumerate() {
+            let (applied, outcome) = match step.action {
+                ReplayAction::Move(direction) => {
+                    let outcome = game.apply_move(direction);
+                    (outcome.moved, outcome)
+                }
+                ReplayAction::Undo => (game.undo(), MoveOutcome::default()),
+                ReplayAction::Redo => (game.redo(), MoveOutcome::default()),
+                ReplayAction::KeepPlaying => {
+                    game.keep_playing();
+                    (true, MoveOutcome::default())
+                }
+            };
+            if !applied {
+                return Err(format!("Tekrar gecersiz: {}. adim ({}) uygulanamadi", i + 1, step.action.as_str()));
+            }
+            frames.push(ReplayFrame::capture(&game, Some(*step), outcome));
+        }
+
+        if game.score() != replay.final_score || game.moves() != replay.final_moves {
+            return Err(format!(
+                "Tekrar sonucu tutmuyor: skor {} / hamle {} bekleniyordu, {} / {} bulundu",
+                replay.final_score, replay.final_moves, game.score(), game.moves(),
+            ));
+        }
+        if tile_values(game.board()) != replay.final_board {
+            return Err("Tekrar sonucu tutmuyor: son tahta farkli".to_string());
+        }
+        Ok(Self { replay, frames, position: 0 })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        Self::new(Replay::from_json(json)?)
+    }
+
+    pub fn replay(&self) -> &Replay { &self.replay }
+
+    /// Number of actions; positions run from 0 (the start) to `len`.
+    pub fn len(&self) -> usize { self.frames.len() - 1 }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    pub fn position(&self) -> usize { self.position }
+
+    pub fn frame(&self) -> &ReplayFrame { &self.frames[self.position] }
+
+    pub fn at_end(&self) -> bool { self.position == self.len() }
+
+    /// Plays the next action; `None` at the end.
+    pub fn step_forward(&mut self) -> Option<&ReplayFrame> {
+        if self.at_end() { return None; }
+        self.position += 1;
+        Some(self.frame())
+    }
+
+    /// Takes back the last action played; `None` at the start.
+    pub fn step_back(&mut self) -> Option<&ReplayFrame> {
+        if self.position == 0 { return None; }
+        self.position -= 1;
+        Some(self.frame())
+    }
+
+    /// Jumps to `position`, clamped to the replay.
+    pub fn seek(&mut self, position: usize) -> &ReplayFrame {
+        self.position = position.min(self.len());
+        self.frame()
+    }
+}
@@ -0,0 +1,264 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::application::history::History;
+use crate::application::replay::{ReplayLog, MAX_REPLAY_STEPS};
+use crate::domain::board::{Board, MAX_TILE};
+use crate::domain::config::GameConfig;
+
+/// Bump whenever `GameSnapshot` changes shape. New fields should carry a
+/// `#[serde(default)]` so older self-describing saves still decode; anything
+/// else gets an upgrade arm in `GameSnapshot::load`.
+///
+/// History: 1 = board, moves, history (JSON only; no release wrote binary saves before 2);
+/// 2 = adds config and keep_playing;
+/// 3 = spawn rules on the config and board;
+/// 4 = `size` replaced by `rows` and `cols`;
+/// 5 = the replay log of the game in progress.
+pub const SNAPSHOT_VERSION: u32 = 5;
+
+const BINARY_MAGIC: &[u8; 4] = b"2048";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub version: u32,
+    #[serde(default)]
+    pub config: GameConfig,
+    pub board: Board,
+    pub moves: u32,
+    pub history: History,
+    #[serde(default)]
+    pub keep_playing: bool,
+    #[serde(default)]
+    pub replay: Option<ReplayLog>,
+}
+
+/// An encoded save whose payload can be decoded as any schema version.
+pub trait SnapshotSource {
+    fn version(&self) -> Result<u32, String>;
+    fn decode<T: DeserializeOwned>(self) -> Result<T, String>;
+}
+
+pub struct JsonSource(serde_json::Value);
+
+impl SnapshotSource for JsonSource {
+    fn version(&self) -> Result<u32, String> {
+        self.0.get("version")
+            .and_then(serde_json::Value::as_u64)
+            .map(|v| v as u32)
+            .ok_or_else(|| "Kayit surumu bulunamadi".to_string())
+    }
+
+    fn decode<T: DeserializeOwned>(self) -> Result<T, String> {
+        serde_json::from_value(self.0).map_err(|e| format!("Kayit okunamadi: {}", e))
+    }
+}
+
+/// `b"2048"`, the schema version as little-endian `u32`, then the payload
+/// as MessagePack with named fields; binary saves start at version 2.
+pub struct BinarySource<'a>(&'a [u8]);
+
+impl SnapshotSource for BinarySource<'_> {
+    fn version(&self) -> Result<u32, String> {
+        if self.0.len() < 8 || &self.0[..4] != BINARY_MAGIC {
+            return Err("Gecersiz kayit basligi".to_string());
+        }
+        match u32::from_le_bytes([self.0[4], self.0[5], self.0[6], self.0[7]]) {
+            1 => Err("Kayit surumu desteklenmiyor: 1".to_string()),
+            version => Ok(version),
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(self) -> Result<T, String> {
+        rmp_serde::from_slice(&self.0[8..]).map_err(|e| format!("Kayit okunamadi: {}", e))
+    }
+}
+
+impl GameSnapshot {
+    pub fn new(config: GameConfig, board: Board, moves: u32, history: History, keep_playing: bool) -> Self {
+        Self { version: SNAPSHOT_VERSION, config, board, moves, history, keep_playing, replay: None }
+    }
+
+    /// Decodes a save of any known version and upgrades it to the current one.
+    pub fn load(source: impl SnapshotSource) -> Result<Self, String> {
+        let mut snapshot: GameSnapshot = match source.version()? {
+            1 => legacy::SnapshotV3::from(source.decode::<legacy::SnapshotV1>()?).into(),
+            2 | 3 => source.decode::<legacy::SnapshotV3>()?.into(),
+            4..=SNAPSHOT_VERSION => source.decode()?,
+            v => return Err(format!("Kayit surumu desteklenmiyor: {}", v)),
+        };
+        snapshot.version = SNAPSHOT_VERSION;
+        snapshot.replay = snapshot.replay.filter(|r| r.steps.len() <= MAX_REPLAY_STEPS);
+        snapshot.validate()?;
+        Ok(snapshot)
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value = serde_json::from_str(json).map_err(|e| format!("Kayit okunamadi: {}", e))?;
+        Self::load(JsonSource(value))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut out = BINARY_MAGIC.to_vec();
+        out.extend_from_slice(&self.version.to_le_bytes());
+        rmp_serde::encode::write_named(&mut out, self).map_err(|e| e.to_string())?;
+        Ok(out)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        Self::load(BinarySource(bytes))
+    }
+
+    /// Checks the config, then the live board and every history board
+    /// against it, so an edited save cannot swap in a board the game would
+    /// trip over.
+    fn validate(&self) -> Result<(), String> {
+        self.config.validate().map_err(|e| format!("Kayittaki ayarlar gecersiz: {}", e))?;
+        std::iter::once(&self.board).chain(self.history.boards()).try_for_each(|b| self.check_board(b))
+    }
+
+    /// Dimensions, tile values, and the win target and spawn rules the board
+    /// plays by, which must be the config's.
+    fn check_board(&self, board: &Board) -> Result<(), String> {
+        let (rows, cols) = (self.config.rows, self.config.cols);
+        if board.rows != rows || board.cols != cols || board.cells.len() != rows * cols {
+            return Err("Kayittaki tahta boyutu tutarsiz".to_string());
+        }
+        if board.win_target != self.config.win_target || board.spawn != self.config.spawn {
+            return Err("Kayittaki tahta kurallari ayarlarla tutmuyor".to_string());
+        }
+        if let Some(tile) = board.cells.iter().flatten().find(|t| !(2..=MAX_TILE).contains(&t.value) || !t.value.is_power_of_two()) {
+            return Err(format!("Kayitta gecersiz tas: {}", tile.value));
+        }
+        Ok(())
+    }
+}
+
+/// Layouts of older schema versions, kept only to decode and upgrade them.
+/// Each one converts into the next, so an old save is upgraded step by step.
+mod legacy {
+    use std::collections::VecDeque;
+
+    use serde::Deserialize;
+
+    use crate::application::history::History;
+    use crate::domain::board::{Board, Tile};
+    use crate::domain::config::{GameConfig, DEFAULT_WIN_TARGET};
+    use crate::domain::rng::GameRng;
+    use crate::domain::spawn::SpawnRules;
+
+    /// Version 1.
+    #[derive(Deserialize)]
+    pub struct SnapshotV1 {
+        board: BoardV1,
+        moves: u32,
+        history: HistoryV1,
+    }
+
+    #[derive(Deserialize)]
+    pub struct BoardV1 {
+        size: usize,
+        cells: Vec<Option<Tile>>,
+        score: u32,
+        rng: GameRng,
+    }
+
+    #[derive(Deserialize)]
+    pub struct HistoryV1 {
+        limit: Option<usize>,
+        undo: VecDeque<BoardV1>,
+        redo: Vec<BoardV1>,
+    }
+
+    /// Versions 2 and 3: square boards with a single `size`.
+    #[derive(Deserialize)]
+    pub struct SnapshotV3 {
+        config: ConfigV3,
+        board: BoardV3,
+        moves: u32,
+        history: HistoryV3,
+        #[serde(default)]
+        keep_playing: bool,
+    }
+
+    #[derive(Deserialize)]
+    pub struct ConfigV3 {
+        size: usize,
+        #[serde(default = "default_win_target")]
+        win_target: u32,
+        #[serde(default)]
+        seed: Option<u64>,
+        #[serde(default)]
+        spawn: SpawnRules,
+    }
+
+    #[derive(Deserialize)]
+    pub struct BoardV3 {
+        size: usize,
+        cells: Vec<Option<Tile>>,
+        score: u32,
+        rng: GameRng,
+        #[serde(default = "default_win_target")]
+        win_target: u32,
+        #[serde(default)]
+        spawn: SpawnRules,
+    }
+
+    #[derive(Deserialize)]
+    pub struct HistoryV3 {
+        limit: Option<usize>,
+        undo: VecDeque<BoardV3>,
+        redo: Vec<BoardV3>,
+    }
+
+    fn default_win_target() -> u32 { DEFAULT_WIN_TARGET }
+
+    impl From<BoardV1> for BoardV3 {
+        fn from(b: BoardV1) -> Self {
+            BoardV3 { size: b.size, cells: b.cells, score: b.score, rng: b.rng, win_target: DEFAULT_WIN_TARGET, spawn: SpawnRules::default() }
+        }
+    }
+
+    impl From<SnapshotV1> for SnapshotV3 {
+        fn from(s: SnapshotV1) -> Self {
+            let config = ConfigV3 { size: s.board.size, win_target: DEFAULT_WIN_TARGET, seed: None, spawn: SpawnRules::default() };
+            let history = HistoryV3 {
+                limit: s.history.limit,
+                undo: s.history.undo.into_iter().map(BoardV3::from).collect(),
+                redo: s.history.redo.into_iter().map(BoardV3::from).collect(),
+            };
+            SnapshotV3 { config, board: s.board.into(), moves: s.moves, history, keep_playing: false }
+        }
+    }
+
+    impl From<BoardV3> for Board {
+        fn from(b: BoardV3) -> Self {
+            Board {
+                rows: b.size,
+                cols: b.size,
+                cells: b.cells,
+                score: b.score,
+                rng: b.rng,
+                win_target: b.win_target,
+                spawn: b.spawn,
+            }
+        }
+    }
+
+    impl From<SnapshotV3> for super::GameSnapshot {
+        fn from(s: SnapshotV3) -> Self {
+            let c = s.config;
+            let config = GameConfig { win_target: c.win_target, seed: c.seed, spawn: c.spawn, ..GameConfig::new(c.size) };
+            let history = History::from_parts(
+                s.history.limit,
+                s.history.undo.into_iter().map(Board::from).collect(),
+                s.history.redo.into_iter().map(Board::from).collect(),
+            );
+            super::GameSnapshot::new(config, s.board.into(), s.moves, history, s.keep_playing)
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Plays a game with a strategy while recording it, then writes the game as
+//! an animated GIF or APNG.
+//!
+//! ```text
+//! cargo run --release --bin record -- --strategy expectimax --max-moves 300 --out game.gif
+//! ```
+//!
+//! Options: `--strategy random|greedy|corner|expectimax|montecarlo|ntuple`,
+//! `--seed S`, `--size N` or `--rows R --cols C`, `--depth D` (expectimax),
+//! `--rollouts N` (montecarlo), `--weights PATH` (ntuple), `--max-moves N` (0 plays to the end),
+//! `--format gif|apng` (default: from the `--out` extension), `--fps N`,
+//! `--width W`, `--height H`, `--out PATH` and `--replay PATH` to also write
+//! the game as a replay file.
+
+use std::process::ExitCode;
+
+use game_2048::{
+    export_recording, strategy_by_name, AnimationFormat, ExportOptions, GameConfig, GameService, NTupleNetwork, StrategyOptions,
+};
+
+struct Options {
+    strategy: String,
+    strategy_options: StrategyOptions,
+    config: GameConfig,
+    max_moves: u32,
+    format: Option<AnimationFormat>,
+    export: ExportOptions,
+    out: String,
+    replay: Option<String>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        strategy: "expectimax".to_string(),
+        strategy_options: StrategyOptions::default(),
+        config: GameConfig { seed: Some(1), ..GameConfig::default() },
+        max_moves: 200,
+        format: None,
+        export: ExportOptions::default(),
+        out: "game.gif".to_string(),
+        replay: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("{} icin deger eksik", flag))?;
+        let number = || value.parse::<u32>().map_err(|e| format!("{}: {}", flag, e));
+        match flag.as_str() {
+            "--strategy" => options.strategy = value.clone(),
+            "--seed" => options.config.seed = Some(value.parse().map_err(|e| format!("--seed: {}", e))?),
+            "--size" => (options.config.rows, options.config.cols) = (number()? as usize, number()? as usize),
+            "--rows" => options.config.rows = number()? as usize,
+            "--cols" => options.config.cols = number()? as usize,
+            "--depth" => options.strategy_options.depth = number()?,
+            "--rollouts" => options.strategy_options.rollouts = number()? as usize,
+            "--weights" => {
+                let bytes = std::fs::read(&value).map_err(|e| format!("{} okunamadi: {}", value, e))?;
+                options.strategy_options.ntuple = Some(NTupleNetwork::from_bytes(&bytes)?);
+            }
+            "--max-moves" => options.max_moves = number()?,
+            "--format" => options.format = Some(value.parse()?),
+            "--fps" => options.export.fps = number()?,
+            "--width" => options.export.width = number()?,
+            "--height" => options.export.height = number()?,
+            "--out" => options.out = value.clone(),
+            "--replay" => options.replay = Some(value.clone()),
+            _ => return Err(format!("Bilinmeyen secenek: {}", flag)),
+        }
+    }
+    options.config.validate()?;
+    options.export.validate()?;
+    Ok(options)
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let format = match options.format {
+        Some(format) => format,
+        None => options.out.rsplit('.').next().unwrap_or("").parse()?,
+    };
+    let strategy_options = StrategyOptions { seed: options.config.seed.unwrap_or(0), ..options.strategy_options };
+    let mut strategy = strategy_by_name(&options.strategy, &strategy_options)?;
+    strategy.supports(options.config.rows, options.config.cols)?;
+    let mut game = GameService::with_config(options.config);
+    game.set_undo_depth(Some(0));
+    game.keep_playing();
+    game.start_recording();
+
+    while options.max_moves == 0 || game.moves() < options.max_moves {
+        let Some((direction, _)) = strategy.choose(game.board()) else { break };
+        game.apply_move(direction);
+    }
+
+    let recording = game.stop_recording().expect("recording was started");
+    let bytes = export_recording(&recording, format, &options.export)?;
+    std::fs::write(&options.out, &bytes).map_err(|e| format!("{} yazilamadi: {}", options.out, e))?;
+    if let (Some(path), Some(replay)) = (&options.replay, game.replay()) {
+        std::fs::write(path, replay.to_json()?).map_err(|e| format!("{} yazilamadi: {}", path, e))?;
+    }
+    println!("{} hamle, skor {} -> {} ({} KB)", game.moves(), game.score(), options.out, bytes.len() / 1024);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Checks a replay file by re-simulating it, and steps through its moves.
+//!
+//! ```text
+//! cargo run --release --bin replay -- game.replay.json
+//! cargo run --release --bin replay -- game.replay.json --step
+//! ```
+//!
+//! Without options it prints whether the replay plays out to its stored
+//! result and shows the final board. `--at N` shows the board after action
+//! `N` instead; `--step` reads commands from stdin: Enter or `n` for the next
+//! action, `p` for the previous one, `g N` to jump, `q` to quit.
+
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use game_2048::{Board, ReplayPlayer};
+
+struct Options {
+    path: String,
+    at: Option<usize>,
+    step: bool,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut path = None;
+    let mut options = Options { path: String::new(), at: None, step: false };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--step" => options.step = true,
+            "--at" => {
+                let value = args.next().ok_or("--at icin deger eksik")?;
+                options.at = Some(value.parse().map_err(|e| format!("--at: {}", e))?);
+            }
+            flag if flag.starts_with("--") => return Err(format!("Bilinmeyen secenek: {}", flag)),
+            _ => path = Some(arg),
+        }
+    }
+    options.path = path.ok_or("Tekrar dosyasi belirtilmedi")?;
+    Ok(options)
+}
+
+fn print_board(board: &Board) {
+    let width = board.cells.iter().flatten().map(|t| t.value.to_string().len()).max().unwrap_or(1).max(4);
+    for r in 0..board.rows {
+        let row: Vec<String> = (0..board.cols)
+            .map(|c| board.get(r, c).as_ref().map_or(".".to_string(), |t| t.value.to_string()))
+            .map(|s| format!("{:>w$}", s, w = width))
+            .collect();
+        println!("{}", row.join(" "));
+    }
+}
+
+fn print_frame(player: &ReplayPlayer) {
+    let frame = player.frame();
+    let action = frame.step.map_or("baslangic".to_string(), |s| format!("{} ({:.1} sn)", s.action.as_str(), s.t_ms as f64 / 1000.0));
+    println!("Adim {}/{}: {}  Skor: {}  Hamle: {}", player.position(), player.len(), action, frame.board.score, frame.moves);
+    print_board(&frame.board);
+}
+
+fn step(player: &mut ReplayPlayer) -> Result<(), String> {
+    print_frame(player);
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).map_err(|e| e.to_string())? == 0 { return Ok(()); }
+        let mut words = line.split_whitespace();
+        match words.next() {
+            None | Some("n") => { if player.step_forward().is_none() { println!("Tekrarin sonu"); continue; } }
+            Some("p") => { if player.step_back().is_none() { println!("Tekrarin basi"); continue; } }
+            Some("g") => match words.next().and_then(|n| n.parse().ok()) {
+                Some(position) => { player.seek(position); }
+                None => { println!("Kullanim: g N"); continue; }
+            },
+            Some("q") => return Ok(()),
+            Some(other) => { println!("Bilinmeyen komut: {} (n, p, g N, q)", other); continue; }
+        }
+        print_frame(player);
+    }
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let json = std::fs::read_to_string(&options.path).map_err(|e| format!("{} okunamadi: {}", options.path, e))?;
+    let mut player = ReplayPlayer::from_json(&json)?;
+    let replay = player.replay();
+    println!(
+        "Gecerli tekrar: {}x{}, tohum {}, {} adim, skor {}, {} hamle",
+        replay.config.rows, replay.config.cols, replay.seed, player.len(), replay.final_score, replay.final_moves,
+    );
+    if options.step {
+        if let Some(at) = options.at { player.seek(at); }
+        return step(&mut player);
+    }
+    player.seek(options.at.unwrap_or(player.len()));
+    print_frame(&player);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Plays many headless games with one strategy and reports statistics.
+//!
+//! ```text
+//! cargo run --release --bin simulate -- --strategy expectimax --games 100 --format json
+//! ```
+//!
+//! Options: `--strategy random|greedy|corner|expectimax|montecarlo|ntuple`,
+//! `--games N`, `--seed S` (game `i` uses seed `S + i`), `--size N` or
+//! `--rows R --cols C`, `--depth D` (expectimax), `--rollouts N`
+//! (montecarlo), `--weights PATH` (ntuple), `--format text|json|csv` and
+//! `--out PATH` (default: stdout).
+//!
+//! `text` and `json` print the summary (`json` also lists every game);
+//! `csv` prints one row per game.
+
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use game_2048::{
+    strategy_by_name, GameConfig, GameService, NTupleNetwork, StrategyOptions,
+};
+
+const WIN_TILES: [u32; 3] = [2048, 4096, 8192];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+struct Options {
+    strategy: String,
+    games: u32,
+    seed: u64,
+    rows: usize,
+    cols: usize,
+    depth: u32,
+    rollouts: usize,
+    weights: Option<String>,
+    format: Format,
+    out: Option<String>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        strategy: "expectimax".to_string(),
+        games: 100,
+        seed: 1,
+        rows: 4,
+        cols: 4,
+        depth: 2,
+        rollouts: 50,
+        weights: None,
+        format: Format::Text,
+        out: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} icin deger eksik", flag));
+        match flag.as_str() {
+            "--strategy" => options.strategy = value()?,
+            "--games" => options.games = value()?.parse().map_err(|e| format!("--games: {}", e))?,
+            "--seed" => options.seed = value()?.parse().map_err(|e| format!("--seed: {}", e))?,
+            "--size" => {
+                let size = value()?.parse().map_err(|e| format!("--size: {}", e))?;
+                (options.rows, options.cols) = (size, size);
+            }
+            "--rows" => options.rows = value()?.parse().map_err(|e| format!("--rows: {}", e))?,
+            "--cols" => options.cols = value()?.parse().map_err(|e| format!("--cols: {}", e))?,
+            "--depth" => options.depth = value()?.parse().map_err(|e| format!("--depth: {}", e))?,
+            "--rollouts" => options.rollouts = value()?.parse().map_err(|e| format!("--rollouts: {}", e))?,
+            "--weights" => options.weights = Some(value()?),
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    other => return Err(format!("Bilinmeyen bicim: {}", other)),
+                }
+            }
+            "--out" => options.out = Some(value()?),
+            _ => return Err(format!("Bilinmeyen secenek: {}", flag)),
+        }
+    }
+    Ok(options)
+}
+
+#[derive(Serialize)]
+struct GameResult {
+    seed: u64,
+    score: u32,
+    max_tile: u32,
+    moves: u32,
+    millis: f64,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    strategy: String,
+    rows: usize,
+    cols: usize,
+    games: usize,
+    mean_score: f64,
+    median_score: f64,
+    max_score: u32,
+    /// Games per largest tile reached.
+    max_tiles: BTreeMap<u32, u32>,
+    /// Share of games reaching each tile, from 0 to 1.
+    win_rates: BTreeMap<u32, f64>,
+    moves_per_second: f64,
+}
+
+/// Strategies are rebuilt per game from its seed, so every game is reproducible on its own.
+fn play(options: &Options, strategy: &StrategyOptions) -> Result<GameResult, String> {
+    let seed = strategy.seed;
+    let mut strategy = strategy_by_name(&options.strategy, strategy)?;
+    // a strategy that cannot read the board would record empty games as results
+    strategy.supports(options.rows, options.cols)?;
+    let config = GameConfig { seed: Some(seed), ..GameConfig::rect(options.rows, options.cols) };
+    config.validate()?;
+    let mut game = GameService::with_config(config);
+    game.set_undo_depth(Some(0));
+    game.keep_playing();
+
+    let started = Instant::now();
+    while let Some((direction, _)) = strategy.choose(game.board()) {
+        if !game.apply_move(direction).moved {
+            return Err(format!("{} gecersiz bir hamle secti: {:?}", strategy.name(), direction));
+        }
+    }
+    let max_tile = game.board().cells.iter().flatten().map(|t| t.value).max().unwrap_or(0);
+    Ok(GameResult { seed, score: game.score(), max_tile, moves: game.moves(), millis: started.elapsed().as_secs_f64() * 1000.0 })
+}
+
+fn summarize(options: &Options, results: &[GameResult]) -> Summary {
+    let n = results.len().max(1) as f64;
+    let mut scores: Vec<u32> = results.iter().map(|r| r.score).collect();
+    scores.sort_unstable();
+    let median_score = match scores.len() {
+        0 => 0.0,
+        len if len % 2 == 0 => (scores[len / 2 - 1] as f64 + scores[len / 2] as f64) / 2.0,
+        len => scores[len / 2] as f64,
+    };
+
+    let mut max_tiles = BTreeMap::new();
+    for r in results {
+        *max_tiles.entry(r.max_tile).or_insert(0) += 1;
+    }
+    let win_rates = WIN_TILES.iter()
+        .map(|&tile| (tile, results.iter().filter(|r| r.max_tile >= tile).count() as f64 / n))
+        .collect();
+
+    let moves: u64 = results.iter().map(|r| r.moves as u64).sum();
+    let seconds: f64 = results.iter().map(|r| r.millis).sum::<f64>() / 1000.0;
+
+    Summary {
+        strategy: options.strategy.clone(),
+        rows: options.rows,
+        cols: options.cols,
+        games: results.len(),
+        mean_score: scores.iter().map(|&s| s as f64).sum::<f64>() / n,
+        median_score,
+        max_score: scores.last().copied().unwrap_or(0),
+        max_tiles,
+        win_rates,
+        moves_per_second: if seconds > 0.0 { moves as f64 / seconds } else { 0.0 },
+    }
+}
+
+fn render(format: Format, summary: &Summary, results: &[GameResult]) -> Result<String, String> {
+    match format {
+        Format::Json => {
+            #[derive(Serialize)]
+            struct Report<'a> {
+                summary: &'a Summary,
+                games: &'a [GameResult],
+            }
+            serde_json::to_string_pretty(&Report { summary, games: results }).map_err(|e| e.to_string())
+        }
+        Format::Csv => {
+            let mut out = String::from("seed,score,max_tile,moves,millis\n");
+            for r in results {
+                out.push_str(&format!("{},{},{},{},{:.3}\n", r.seed, r.score, r.max_tile, r.moves, r.millis));
+            }
+            Ok(out)
+        }
+        Format::Text => {
+            let mut out = format!("{} | {}x{} | {} oyun\n", summary.strategy, summary.rows, summary.cols, summary.games);
+            out.push_str(&format!("skor: ort. {:.0}, medyan {:.0}, en yuksek {}\n", summary.mean_score, summary.median_score, summary.max_score));
+            for (tile, rate) in &summary.win_rates {
+                out.push_str(&format!("{:>5} ve ustu: {:>5.1}%\n", tile, rate * 100.0));
+            }
+            out.push_str("en buyuk tas dagilimi:\n");
+            for (tile, count) in &summary.max_tiles {
+                out.push_str(&format!("{:>7}: {}\n", tile, count));
+            }
+            out.push_str(&format!("saniyede {:.0} hamle\n", summary.moves_per_second));
+            Ok(out)
+        }
+    }
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let ntuple = match &options.weights {
+        Some(path) => {
+            let bytes = std::fs::read(path).map_err(|e| format!("{} okunamadi: {}", path, e))?;
+            Some(NTupleNetwork::from_bytes(&bytes)?)
+        }
+        None => None,
+    };
+    let mut strategy = StrategyOptions { depth: options.depth, rollouts: options.rollouts, ntuple, ..StrategyOptions::default() };
+
+    let results = (0..options.games as u64)
+        .map(|i| {
+            strategy.seed = options.seed.wrapping_add(i);
+            play(&options, &strategy)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let report = render(options.format, &summarize(&options, &results), &results)?;
+
+    match &options.out {
+        Some(path) => std::fs::write(path, report).map_err(|e| format!("{} yazilamadi: {}", path, e)),
+        None => {
+            print!("{}", report);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Trains an n-tuple network by self-play and writes its weights.
+//!
+//! ```text
+//! cargo run --release --bin train_ntuple -- --episodes 100000 --out ntuple.bin
+//! ```
+//!
+//! Options: `--episodes N`, `--alpha A`, `--seed S`, `--out PATH`,
+//! `--resume PATH` (continue from a weight file) and
+//! `--tuples "0,1,2,3;4,5,6,7"` (cell indices `row * 4 + col`).
+
+use std::process::ExitCode;
+
+use game_2048::{Board, GameConfig, NTupleNetwork};
+
+const REPORT_EVERY: u32 = 1000;
+
+struct Options {
+    episodes: u32,
+    alpha: f64,
+    seed: u64,
+    out: String,
+    resume: Option<String>,
+    tuples: Option<Vec<Vec<usize>>>,
+}
+
+fn parse_tuples(spec: &str) -> Result<Vec<Vec<usize>>, String> {
+    spec.split(';')
+        .map(|tuple| tuple.split(',').map(|c| c.trim().parse().map_err(|_| format!("Gecersiz hucre: {}", c))).collect())
+        .collect()
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options { episodes: 10_000, alpha: 0.1, seed: 1, out: "ntuple.bin".to_string(), resume: None, tuples: None };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} icin deger eksik", flag));
+        match flag.as_str() {
+            "--episodes" => options.episodes = value()?.parse().map_err(|e| format!("--episodes: {}", e))?,
+            "--alpha" => options.alpha = value()?.parse().map_err(|e| format!("--alpha: {}", e))?,
+            "--seed" => options.seed = value()?.parse().map_err(|e| format!("--seed: {}", e))?,
+            "--out" => options.out = value()?,
+            "--resume" => options.resume = Some(value()?),
+            "--tuples" => options.tuples = Some(parse_tuples(&value()?)?),
+            _ => return Err(format!("Bilinmeyen secenek: {}", flag)),
+        }
+    }
+    Ok(options)
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let mut network = match (&options.resume, options.tuples) {
+        (Some(path), _) => {
+            let bytes = std::fs::read(path).map_err(|e| format!("{} okunamadi: {}", path, e))?;
+            NTupleNetwork::from_bytes(&bytes)?
+        }
+        (None, Some(tuples)) => NTupleNetwork::new(tuples)?,
+        (None, None) => NTupleNetwork::default(),
+    };
+
+    let (mut total_score, mut best_tile, mut reached_2048) = (0u64, 0u32, 0u32);
+    for episode in 1..=options.episodes {
+        let config = GameConfig { seed: Some(options.seed.wrapping_add(episode as u64)), ..GameConfig::new(4) };
+        let board = network.train_episode(Board::from_config(&config), options.alpha)?;
+
+        let max_tile = board.cells.iter().flatten().map(|t| t.value).max().unwrap_or(0);
+        total_score += board.score as u64;
+        best_tile = best_tile.max(max_tile);
+        if max_tile >= 2048 { reached_2048 += 1; }
+
+        if episode % REPORT_EVERY == 0 || episode == options.episodes {
+            let played = (episode - 1) % REPORT_EVERY + 1;
+            println!(
+                "{:>8} oyun | ort. skor {:>8.0} | 2048 orani {:>5.1}% | en buyuk tas {}",
+                episode,
+                total_score as f64 / played as f64,
+                100.0 * reached_2048 as f64 / played as f64,
+                best_tile,
+            );
+            (total_score, best_tile, reached_2048) = (0, 0, 0);
+        }
+    }
+
+    std::fs::write(&options.out, network.to_bytes()).map_err(|e| format!("{} yazilamadi: {}", options.out, e))?;
+    println!("Agirliklar kaydedildi: {}", options.out);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Plays the game in a terminal, with the same colours as the canvas.
+//!
+//! ```text
+//! cargo run --release --bin tui -- --size 4
+//! ```
+//!
+//! Options: `--size N` or `--rows R --cols C`, `--seed S`, `--replay PATH`
+//! to write the game as a replay file on quitting.
+//! Keys: arrows, WASD or HJKL to move; U/Z undo, Y redo; C or Enter to
+//! keep playing after a win; R restarts; Q or Esc quits.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod terminal {
+    use std::io::{self, Write};
+    use std::process::ExitCode;
+
+    use crossterm::cursor::{Hide, MoveTo, Show};
+    use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+    use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+    use crossterm::{execute, queue};
+
+    use game_2048::{palette, Direction, GameConfig, GameService};
+
+    /// Characters per tile across and lines per tile down.
+    const TILE_W: u16 = 8;
+    const TILE_H: u16 = 3;
+    /// Board background left between tiles, as the canvas pads them.
+    const GAP: u16 = 1;
+    /// Lines above the grid (title, score) and below it (status, keys).
+    const HEADER: u16 = 2;
+    const FOOTER: u16 = 3;
+
+    fn color(hex: &str) -> Color {
+        let (r, g, b) = palette::rgb(hex);
+        Color::Rgb { r, g, b }
+    }
+
+    /// Raw mode and the alternate screen for as long as it lives, restored
+    /// even when the game panics.
+    struct Screen;
+
+    impl Screen {
+        fn enter() -> io::Result<Self> {
+            terminal::enable_raw_mode()?;
+            execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+            Ok(Screen)
+        }
+    }
+
+    impl Drop for Screen {
+        fn drop(&mut self) {
+            let _ = execute!(io::stdout(), ResetColor, Show, LeaveAlternateScreen);
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+
+    enum Action {
+        Move(Direction),
+        Undo,
+        Redo,
+        Continue,
+        Restart,
+        Quit,
+    }
+
+    fn action(key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        Some(match key.code {
+            KeyCode::Left | KeyCode::Char('a' | 'A' | 'h' | 'H') => Action::Move(Direction::Left),
+            KeyCode::Right | KeyCode::Char('d' | 'D' | 'l' | 'L') => Action::Move(Direction::Right),
+            KeyCode::Up | KeyCode::Char('w' | 'W' | 'k' | 'K') => Action::Move(Direction::Up),
+            KeyCode::Down | KeyCode::Char('s' | 'S' | 'j' | 'J') => Action::Move(Direction::Down),
+            KeyCode::Char('u' | 'U' | 'z' | 'Z') => Action::Undo,
+            KeyCode::Char('y' | 'Y') => Action::Redo,
+            KeyCode::Char('c' | 'C') | KeyCode::Enter => Action::Continue,
+            KeyCode::Char('r' | 'R') => Action::Restart,
+            KeyCode::Char('q' | 'Q') | KeyCode::Esc => Action::Quit,
+            _ => return None,
+        })
+    }
+
+    fn draw(out: &mut impl Write, game: &GameService) -> io::Result<()> {
+        let board = game.board();
+        let (width, height) = terminal::size()?;
+        let grid_w = board.cols as u16 * (TILE_W + GAP) + GAP;
+        let grid_h = board.rows as u16 * (TILE_H + GAP) + GAP;
+        queue!(out, ResetColor, Clear(ClearType::All))?;
+
+        if width < grid_w || height < grid_h + HEADER + FOOTER {
+            queue!(out, MoveTo(0, 0), Print("Pencere çok küçük, lütfen büyütün"))?;
+            return out.flush();
+        }
+
+        let left = (width - grid_w) / 2;
+        let top = (height - grid_h - HEADER - FOOTER) / 2 + HEADER;
+        queue!(
+            out,
+            MoveTo(left, top - HEADER),
+            Print(format!("2048  Skor: {}  En iyi: {}  Hamle: {}", game.score(), game.best_score(), game.moves())),
+        )?;
+
+        queue!(out, SetBackgroundColor(color(palette::BACKGROUND)))?;
+        for line in 0..grid_h {
+            queue!(out, MoveTo(left, top + line), Print(" ".repeat(grid_w as usize)))?;
+        }
+        for r in 0..board.rows {
+            for c in 0..board.cols {
+                let x = left + GAP + c as u16 * (TILE_W + GAP);
+                let y = top + GAP + r as u16 * (TILE_H + GAP);
+                let (bg, fg, label) = match board.get(r, c) {
+                    Some(tile) => (palette::tile_color(tile.value), palette::tile_text_color(tile.value), tile.value.to_string()),
+                    None => (palette::EMPTY_CELL, palette::TEXT, String::new()),
+                };
+                queue!(out, SetBackgroundColor(color(bg)), SetForegroundColor(color(fg)))?;
+                for line in 0..TILE_H {
+                    let text = if line == TILE_H / 2 { label.as_str() } else { "" };
+                    queue!(out, MoveTo(x, y + line), Print(format!("{:^w$}", text, w = TILE_W as usize)))?;
+                }
+            }
+        }
+        queue!(out, ResetColor)?;
+
+        let status = if game.win_pending() {
+            "Kazandın! Devam: C  ·  Yeni oyun: R"
+        } else if game.is_over() {
+            "Oyun bitti. Yeni oyun: R"
+        } else {
+            ""
+        };
+        queue!(
+            out,
+            MoveTo(left, top + grid_h + 1),
+            Print(status),
+            MoveTo(left, top + grid_h + 2),
+            Print("Ok/WASD/HJKL: hareket  U: geri al  Y: yinele  R: yeni oyun  Q: çıkış"),
+        )?;
+        out.flush()
+    }
+
+    fn parse_args() -> Result<(GameConfig, Option<String>), String> {
+        let mut config = GameConfig::default();
+        let mut replay = None;
+        let mut args = std::env::args().skip(1);
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or_else(|| format!("{} icin deger eksik", flag))?;
+            let number = || value.parse::<usize>().map_err(|e| format!("{}: {}", flag, e));
+            match flag.as_str() {
+                "--size" => (config.rows, config.cols) = (number()?, number()?),
+                "--rows" => config.rows = number()?,
+                "--cols" => config.cols = number()?,
+                "--seed" => config.seed = Some(value.parse().map_err(|e| format!("--seed: {}", e))?),
+                "--replay" => replay = Some(value.clone()),
+                _ => return Err(format!("Bilinmeyen secenek: {}", flag)),
+            }
+        }
+        config.validate()?;
+        Ok((config, replay))
+    }
+
+    fn play(game: &mut GameService) -> io::Result<()> {
+        let _screen = Screen::enter()?;
+        let mut out = io::stdout();
+        draw(&mut out, game)?;
+        loop {
+            match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    match action(key) {
+                        Some(Action::Move(direction)) => { game.apply_move(direction); }
+                        Some(Action::Undo) => { game.undo(); }
+                        Some(Action::Redo) => { game.redo(); }
+                        Some(Action::Continue) => { if game.win_pending() { game.keep_playing(); } }
+                        Some(Action::Restart) => game.reset(),
+                        Some(Action::Quit) => return Ok(()),
+                        None => continue,
+                    }
+                    draw(&mut out, game)?;
+                }
+                Event::Resize(_, _) => draw(&mut out, game)?,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn main() -> ExitCode {
+        let (config, replay_path) = match parse_args() {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        let mut game = GameService::with_config(config);
+        match play(&mut game) {
+            Ok(()) => {
+                println!("Skor: {}  Hamle: {}", game.score(), game.moves());
+                match (replay_path, game.replay()) {
+                    (Some(path), Some(replay)) => match replay.to_json().and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string())) {
+                        Ok(()) => ExitCode::SUCCESS,
+                        Err(e) => {
+                            eprintln!("{} yazilamadi: {}", path, e);
+                            ExitCode::FAILURE
+                        }
+                    },
+                    _ => ExitCode::SUCCESS,
+                }
+            }
+            Err(e) => {
+                eprintln!("Terminal hatasi: {}", e);
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> std::process::ExitCode {
+    terminal::main()
+}
+
+/// The terminal needs a native target; built for wasm this does nothing.
+#[cfg(target_arch = "wasm32")]
+fn main() {}
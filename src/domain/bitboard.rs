@@ -0,0 +1,214 @@
+use std::sync::OnceLock;
+
+use super::board::{Board, Tile};
+use super::direction::Direction;
+use super::rng::GameRng;
+use super::spawn::SpawnRules;
+
+/// Compact 4x4 board: sixteen 4-bit exponents packed into a `u64`. Row `r`
+/// lives in bits `16*r..16*r+16`, column `c` in the `c`-th nibble of its row,
+/// and a nibble of 0 is an empty cell. Tiles up to 32768 (2^15) fit; two
+/// 32768 tiles do not merge here, which is the only divergence from `Board`.
+///
+/// Moves are table lookups, so expectimax, Monte Carlo rollouts and the
+/// n-tuple network use it for 4x4 games and fall back to `Board` for other
+/// sizes; convert to and from `Board` at the edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitBoard(pub u64);
+
+pub const BITBOARD_SIZE: usize = 4;
+const MAX_EXPONENT: u8 = 15;
+/// The largest tile a bitboard holds.
+pub const MAX_BITBOARD_TILE: u32 = 1 << MAX_EXPONENT;
+const COL_MASK: u64 = 0x000F_000F_000F_000F;
+
+struct Tables {
+    /// XOR deltas for a row sliding toward column 0 / column 3.
+    left: Vec<u16>,
+    right: Vec<u16>,
+    /// Same moves for a column word, already spread to one nibble per row.
+    up: Vec<u64>,
+    down: Vec<u64>,
+    score: Vec<u32>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut t = Tables {
+            left: vec![0; 1 << 16],
+            right: vec![0; 1 << 16],
+            up: vec![0; 1 << 16],
+            down: vec![0; 1 << 16],
+            score: vec![0; 1 << 16],
+        };
+        for row in 0..=u16::MAX {
+            let line = unpack_row(row);
+            let (merged, score) = merge_line(line);
+            let left = pack_row(merged);
+            let mut rev = line;
+            rev.reverse();
+            let mut merged_rev = merge_line(rev).0;
+            merged_rev.reverse();
+            let right = pack_row(merged_rev);
+
+            let i = row as usize;
+            t.left[i] = row ^ left;
+            t.right[i] = row ^ right;
+            t.up[i] = spread_column(row ^ left);
+            t.down[i] = spread_column(row ^ right);
+            t.score[i] = score;
+        }
+        t
+    })
+}
+
+fn unpack_row(row: u16) -> [u8; 4] {
+    [(row & 0xF) as u8, ((row >> 4) & 0xF) as u8, ((row >> 8) & 0xF) as u8, ((row >> 12) & 0xF) as u8]
+}
+
+fn pack_row(line: [u8; 4]) -> u16 {
+    line.iter().enumerate().fold(0, |acc, (i, &e)| acc | ((e as u16) << (4 * i)))
+}
+
+/// Slides one line toward index 0, returning the new exponents and the score gained.
+fn merge_line(line: [u8; 4]) -> ([u8; 4], u32) {
+    let tiles: Vec<u8> = line.iter().copied().filter(|&e| e != 0).collect();
+    let mut out = [0u8; 4];
+    let mut score = 0;
+    let (mut i, mut k) = (0, 0);
+    while i < tiles.len() {
+        if i + 1 < tiles.len() && tiles[i] == tiles[i + 1] && tiles[i] < MAX_EXPONENT {
+            out[k] = tiles[i] + 1;
+            score += 1u32 << out[k];
+            i += 2;
+        } else {
+            out[k] = tiles[i];
+            i += 1;
+        }
+        k += 1;
+    }
+    (out, score)
+}
+
+/// Moves the four nibbles of a column word to bits 0, 16, 32 and 48.
+fn spread_column(col: u16) -> u64 {
+    let c = col as u64;
+    (c & 0xF) | ((c & 0xF0) << 12) | ((c & 0xF00) << 24) | ((c & 0xF000) << 36)
+}
+
+impl BitBoard {
+    pub fn get(self, row: usize, col: usize) -> u8 {
+        ((self.0 >> (16 * row + 4 * col)) & 0xF) as u8
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, exponent: u8) {
+        let shift = 16 * row + 4 * col;
+        self.0 = (self.0 & !(0xF << shift)) | (((exponent & 0xF) as u64) << shift);
+    }
+
+    fn row(self, row: usize) -> u16 { (self.0 >> (16 * row)) as u16 }
+
+    fn column(self, col: usize) -> u16 {
+        let x = (self.0 >> (4 * col)) & COL_MASK;
+        (x | (x >> 12) | (x >> 24) | (x >> 36)) as u16
+    }
+
+    /// Slides and merges without spawning; returns the new board and score gained.
+    pub fn slide(self, direction: Direction) -> (BitBoard, u32) {
+        let t = tables();
+        let mut board = self.0;
+        let mut score = 0;
+        match direction {
+            Direction::Left | Direction::Right => {
+                let table = if direction == Direction::Left { &t.left } else { &t.right };
+                for r in 0..BITBOARD_SIZE {
+                    let row = self.row(r) as usize;
+                    board ^= (table[row] as u64) << (16 * r);
+                    score += t.score[row];
+                }
+            }
+            Direction::Up | Direction::Down => {
+                let table = if direction == Direction::Up { &t.up } else { &t.down };
+                for c in 0..BITBOARD_SIZE {
+                    let col = self.column(c) as usize;
+                    board ^= table[col] << (4 * c);
+                    score += t.score[col];
+                }
+            }
+        }
+        (BitBoard(board), score)
+    }
+
+    /// Mirrors `Board::can_move`: any empty cell counts as a possible move.
+    pub fn can_move(self) -> bool {
+        self.empty_count() > 0 || [Direction::Left, Direction::Right, Direction::Up, Direction::Down]
+            .into_iter()
+            .any(|d| self.slide(d).0 != self)
+    }
+
+    pub fn empty_count(self) -> u32 {
+        // fold each nibble into its low bit, then count the nibbles that stayed zero
+        let mut x = self.0;
+        x |= (x >> 2) & 0x3333_3333_3333_3333;
+        x |= x >> 1;
+        (!x & 0x1111_1111_1111_1111).count_ones()
+    }
+
+    /// Empty cells in the same order as `Board::empty_positions`.
+    pub fn empty_positions(self) -> Vec<(usize, usize)> {
+        (0..BITBOARD_SIZE)
+            .flat_map(|row| (0..BITBOARD_SIZE).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.get(row, col) == 0)
+            .collect()
+    }
+
+    /// Spawns up to `count` tiles like `Board::spawn_tiles`, drawing from
+    /// `rng` in the same order, so both boards get the same tiles. Every
+    /// value in `rules` must fit, i.e. be at most `MAX_BITBOARD_TILE`.
+    pub fn spawn_tiles(&mut self, rules: &SpawnRules, count: usize, rng: &mut GameRng) {
+        for _ in 0..count {
+            let empties = self.empty_positions();
+            if empties.is_empty() { return; }
+            let (row, col) = rules.pick_position(&empties, BITBOARD_SIZE, BITBOARD_SIZE, rng);
+            let value = rules.pick_value(rng);
+            self.set(row, col, value.trailing_zeros() as u8);
+        }
+    }
+
+    pub fn max_exponent(self) -> u8 {
+        (0..16).map(|i| ((self.0 >> (4 * i)) & 0xF) as u8).max().unwrap_or(0)
+    }
+
+    /// Overwrites the tiles of a 4x4 `board`, keeping its score and RNG.
+    pub fn apply_to(self, board: &mut Board) {
+        for row in 0..BITBOARD_SIZE {
+            for col in 0..BITBOARD_SIZE {
+                let e = self.get(row, col);
+                board.set(row, col, if e == 0 { None } else { Some(Tile::new(1 << e)) });
+            }
+        }
+    }
+}
+
+impl TryFrom<&Board> for BitBoard {
+    type Error = String;
+
+    fn try_from(board: &Board) -> Result<Self, Self::Error> {
+        if board.rows != BITBOARD_SIZE || board.cols != BITBOARD_SIZE {
+            return Err(format!("Bitboard yalnizca 4x4 tahtalari destekler: {}x{}", board.rows, board.cols));
+        }
+        let mut bits = BitBoard::default();
+        for row in 0..BITBOARD_SIZE {
+            for col in 0..BITBOARD_SIZE {
+                if let Some(tile) = board.get(row, col) {
+                    if !tile.value.is_power_of_two() || tile.value < 2 || tile.value > MAX_BITBOARD_TILE {
+                        return Err(format!("Bitboard'a sigmayan tas: {}", tile.value));
+                    }
+                    bits.set(row, col, tile.value.trailing_zeros() as u8);
+                }
+            }
+        }
+        Ok(bits)
+    }
+}
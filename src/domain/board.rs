@@ -1,8 +1,14 @@
-use rand::Rng;
+use serde::{Deserialize, Serialize};
 
+use super::config::{GameConfig, DEFAULT_WIN_TARGET};
 use super::direction::Direction;
+use super::rng::GameRng;
+use super::spawn::SpawnRules;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// The largest tile a `u32` holds; two of them do not merge.
+pub const MAX_TILE: u32 = 1 << 31;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MoveEvent {
     pub from_row: usize,
     pub from_col: usize,
@@ -12,7 +18,27 @@ pub struct MoveEvent {
     pub merged_into_value: Option<u32>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpawnEvent {
+    pub row: usize,
+    pub col: usize,
+    pub value: u32,
+}
+
+/// Result of a single move. `events` has one entry per tile that took part,
+/// including tiles that stayed in place. `won` is only set on the move that
+/// first reaches the win target.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveOutcome {
+    pub moved: bool,
+    pub score_gained: u32,
+    pub events: Vec<MoveEvent>,
+    pub spawned: Vec<SpawnEvent>,
+    pub won: bool,
+    pub over: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Tile {
     pub value: u32,
     pub merged: bool,
@@ -24,22 +50,50 @@ impl Tile {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Board {
-    pub size: usize,
+    pub rows: usize,
+    pub cols: usize,
     pub cells: Vec<Option<Tile>>, // row-major
     pub score: u32,
+    pub rng: GameRng,
+    #[serde(default = "default_win_target")]
+    pub win_target: u32,
+    #[serde(default)]
+    pub spawn: SpawnRules,
 }
 
+fn default_win_target() -> u32 { DEFAULT_WIN_TARGET }
+
 impl Board {
     pub fn new(size: usize) -> Self {
-        let mut board = Self { size, cells: vec![None; size * size], score: 0 };
-        board.spawn_random_tile();
-        board.spawn_random_tile();
+        Self::from_config(&GameConfig::new(size))
+    }
+
+    pub fn with_seed(size: usize, seed: u64) -> Self {
+        Self::from_config(&GameConfig { seed: Some(seed), ..GameConfig::new(size) })
+    }
+
+    pub fn from_config(config: &GameConfig) -> Self {
+        let rng = config.seed.map_or_else(GameRng::from_entropy, GameRng::new);
+        Self::with_rng(config, rng)
+    }
+
+    pub fn with_rng(config: &GameConfig, rng: GameRng) -> Self {
+        let mut board = Self {
+            rows: config.rows,
+            cols: config.cols,
+            cells: vec![None; config.rows * config.cols],
+            score: 0,
+            rng,
+            win_target: config.win_target,
+            spawn: config.spawn.clone(),
+        };
+        board.spawn_tiles(board.spawn.initial);
         board
     }
 
-    fn index(&self, row: usize, col: usize) -> usize { row * self.size + col }
+    fn index(&self, row: usize, col: usize) -> usize { row * self.cols + col }
 
     pub fn get(&self, row: usize, col: usize) -> &Option<Tile> {
         &self.cells[self.index(row, col)]
@@ -51,17 +105,15 @@ impl Board {
     }
 
     pub fn reset_merged_flags(&mut self) {
-        for cell in &mut self.cells {
-            if let Some(tile) = cell {
-                tile.merged = false;
-            }
+        for tile in self.cells.iter_mut().flatten() {
+            tile.merged = false;
         }
     }
 
     pub fn empty_positions(&self) -> Vec<(usize, usize)> {
         let mut result: Vec<(usize, usize)> = Vec::new();
-        for row in 0..self.size {
-            for col in 0..self.size {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
                 if self.get(row, col).is_none() {
                     result.push((row, col));
                 }
@@ -70,27 +122,31 @@ impl Board {
         result
     }
 
-    pub fn spawn_random_tile(&mut self) -> bool {
+    pub fn spawn_random_tile(&mut self) -> Option<SpawnEvent> {
         let empties = self.empty_positions();
-        if empties.is_empty() { return false; }
-        let mut rng = rand::thread_rng();
-        let &(row, col) = empties.get(rng.gen_range(0..empties.len())).unwrap();
-        let value = if rng.gen_range(0..10) == 0 { 4 } else { 2 };
+        if empties.is_empty() { return None; }
+        let (row, col) = self.spawn.pick_position(&empties, self.rows, self.cols, &mut self.rng);
+        let value = self.spawn.pick_value(&mut self.rng);
         self.set(row, col, Some(Tile::new(value)));
-        true
+        Some(SpawnEvent { row, col, value })
+    }
+
+    /// Spawns up to `count` tiles, stopping early when the board fills up.
+    pub fn spawn_tiles(&mut self, count: usize) -> Vec<SpawnEvent> {
+        (0..count).map_while(|_| self.spawn_random_tile()).collect()
     }
 
     pub fn can_move(&self) -> bool {
         if !self.empty_positions().is_empty() { return true; }
         // check merges
-        for row in 0..self.size {
-            for col in 0..self.size {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
                 if let Some(tile) = self.get(row, col) {
-                    if row + 1 < self.size {
-                        if let Some(down) = self.get(row + 1, col) { if down.value == tile.value { return true; } }
+                    if row + 1 < self.rows && self.get(row + 1, col).as_ref().is_some_and(|down| down.value == tile.value) {
+                        return true;
                     }
-                    if col + 1 < self.size {
-                        if let Some(right) = self.get(row, col + 1) { if right.value == tile.value { return true; } }
+                    if col + 1 < self.cols && self.get(row, col + 1).as_ref().is_some_and(|right| right.value == tile.value) {
+                        return true;
                     }
                 }
             }
@@ -99,150 +155,61 @@ impl Board {
     }
 
     pub fn is_won(&self) -> bool {
-        self.cells.iter().flatten().any(|t| t.value >= 2048)
+        self.cells.iter().flatten().any(|t| t.value >= self.win_target)
     }
 
-    pub fn slide(&mut self, direction: Direction) -> bool {
-        self.reset_merged_flags();
-        let mut moved = false;
-
-        match direction {
-            Direction::Left => {
-                for row in 0..self.size {
-                    moved |= self.compact_line(row, 0, 0, 1);
-                }
-            }
-            Direction::Right => {
-                for row in 0..self.size {
-                    moved |= self.compact_line(row, self.size - 1, 0, -1);
-                }
-            }
-            Direction::Up => {
-                for col in 0..self.size {
-                    moved |= self.compact_column(0, col, 1, 0);
-                }
-            }
-            Direction::Down => {
-                for col in 0..self.size {
-                    moved |= self.compact_column(self.size - 1, col, -1, 0);
-                }
-            }
+    /// Plays one move: slides and merges, spawns a tile if anything changed
+    /// and reports everything a caller needs to animate or score it.
+    pub fn apply_move(&mut self, direction: Direction) -> MoveOutcome {
+        let was_won = self.is_won();
+        let mut outcome = self.shift(direction);
+        if outcome.moved {
+            outcome.spawned = self.spawn_tiles(self.spawn.per_move);
         }
+        outcome.won = !was_won && self.is_won();
+        outcome.over = !self.can_move();
+        outcome
+    }
 
-        if moved {
-            self.spawn_random_tile();
-        }
-        moved
+    pub fn slide(&mut self, direction: Direction) -> bool {
+        self.apply_move(direction).moved
     }
 
-    pub fn slide_with_animations(&mut self, direction: Direction) -> (bool, Vec<MoveEvent>) {
+    /// Slides and merges without spawning, e.g. to evaluate a move.
+    pub fn shift(&mut self, direction: Direction) -> MoveOutcome {
         self.reset_merged_flags();
+        let score_before = self.score;
         let mut moved = false;
         let mut events: Vec<MoveEvent> = Vec::new();
 
         match direction {
             Direction::Left => {
-                for row in 0..self.size {
-                    moved |= self.compact_line_with_events(row, 0, 0, 1, &mut events);
+                for row in 0..self.rows {
+                    moved |= self.compact_line(row, 0, 0, 1, &mut events);
                 }
             }
             Direction::Right => {
-                for row in 0..self.size {
-                    moved |= self.compact_line_with_events(row, self.size - 1, 0, -1, &mut events);
+                for row in 0..self.rows {
+                    moved |= self.compact_line(row, self.cols - 1, 0, -1, &mut events);
                 }
             }
             Direction::Up => {
-                for col in 0..self.size {
-                    moved |= self.compact_column_with_events(0, col, 1, 0, &mut events);
+                for col in 0..self.cols {
+                    moved |= self.compact_column(0, col, 1, 0, &mut events);
                 }
             }
             Direction::Down => {
-                for col in 0..self.size {
-                    moved |= self.compact_column_with_events(self.size - 1, col, -1, 0, &mut events);
+                for col in 0..self.cols {
+                    moved |= self.compact_column(self.rows - 1, col, -1, 0, &mut events);
                 }
             }
         }
 
-        (moved, events)
-    }
-
-    fn compact_line(&mut self, row: usize, _start_col: usize, _row_step: isize, col_step: isize) -> bool {
-        // Gather existing values in scan order
-        let cols: Vec<usize> = if col_step == 1 { (0..self.size).collect() } else { (0..self.size).rev().collect() };
-        let mut values: Vec<(usize, u32)> = Vec::new();
-        for c in &cols {
-            if let Some(t) = self.get(row, *c) { values.push((*c, t.value)); }
-        }
-
-        // Compute merged sequence
-        let mut merged_values: Vec<u32> = Vec::new();
-        let mut i = 0;
-        while i < values.len() {
-            if i + 1 < values.len() && values[i].1 == values[i + 1].1 {
-                let nv = values[i].1 * 2;
-                self.score += nv;
-                merged_values.push(nv);
-                i += 2;
-            } else {
-                merged_values.push(values[i].1);
-                i += 1;
-            }
-        }
-
-        // Write back and detect moved
-        let mut moved = false;
-        // clear row
-        for c in 0..self.size { self.set(row, c, None); }
-        let merged_len = merged_values.len();
-        for k in 0..merged_len {
-            let val = merged_values[k];
-            let dest_c = if col_step == 1 { k } else { self.size - 1 - k };
-            // moved if any original src column for this value is not at dest_c
-            if !values.is_empty() {
-                moved = true; // safe approximation
-            }
-            self.set(row, dest_c, Some(Tile::new(val)));
-        }
-        moved
-    }
-
-    fn compact_column(&mut self, _start_row: usize, col: usize, row_step: isize, _col_step_unused: isize) -> bool {
-        let rows: Vec<usize> = if row_step == 1 { (0..self.size).collect() } else { (0..self.size).rev().collect() };
-        let mut values: Vec<(usize, u32)> = Vec::new();
-        for r in &rows {
-            if let Some(t) = self.get(*r, col) { values.push((*r, t.value)); }
-        }
-
-        let mut merged_values: Vec<u32> = Vec::new();
-        let mut i = 0;
-        while i < values.len() {
-            if i + 1 < values.len() && values[i].1 == values[i + 1].1 {
-                let nv = values[i].1 * 2;
-                self.score += nv;
-                merged_values.push(nv);
-                i += 2;
-            } else {
-                merged_values.push(values[i].1);
-                i += 1;
-            }
-        }
-
-        let mut moved = false;
-        for r in 0..self.size { self.set(r, col, None); }
-        let merged_len = merged_values.len();
-        for k in 0..merged_len {
-            let val = merged_values[k];
-            let dest_r = if row_step == 1 { k } else { self.size - 1 - k };
-            if !values.is_empty() {
-                moved = true;
-            }
-            self.set(dest_r, col, Some(Tile::new(val)));
-        }
-        moved
+        MoveOutcome { moved, score_gained: self.score - score_before, events, ..MoveOutcome::default() }
     }
 
-    fn compact_line_with_events(&mut self, row: usize, _start_col: usize, _row_step: isize, col_step: isize, out: &mut Vec<MoveEvent>) -> bool {
-        let cols: Vec<usize> = if col_step == 1 { (0..self.size).collect() } else { (0..self.size).rev().collect() };
+    fn compact_line(&mut self, row: usize, _start_col: usize, _row_step: isize, col_step: isize, out: &mut Vec<MoveEvent>) -> bool {
+        let cols: Vec<usize> = if col_step == 1 { (0..self.cols).collect() } else { (0..self.cols).rev().collect() };
         let mut items: Vec<(usize, u32)> = Vec::new();
         for c in &cols { if let Some(t) = self.get(row, *c) { items.push((*c, t.value)); } }
 
@@ -250,9 +217,9 @@ impl Board {
         let mut merged: Vec<(Vec<usize>, u32)> = Vec::new();
         let mut i = 0;
         while i < items.len() {
-            if i + 1 < items.len() && items[i].1 == items[i + 1].1 {
+            if i + 1 < items.len() && items[i].1 == items[i + 1].1 && items[i].1 < MAX_TILE {
                 merged.push((vec![items[i].0, items[i + 1].0], items[i].1 * 2));
-                self.score += items[i].1 * 2;
+                self.score = self.score.saturating_add(items[i].1 * 2);
                 i += 2;
             } else {
                 merged.push((vec![items[i].0], items[i].1));
@@ -261,10 +228,10 @@ impl Board {
         }
 
         // Clear row, place and emit events
-        for c in 0..self.size { self.set(row, c, None); }
+        for c in 0..self.cols { self.set(row, c, None); }
         let mut moved = false;
-        for (k, (sources, val)) in (0..merged.len()).zip(merged.into_iter()) {
-            let dest_col = if col_step == 1 { k } else { self.size - 1 - k };
+        for (k, (sources, val)) in (0..merged.len()).zip(merged) {
+            let dest_col = if col_step == 1 { k } else { self.cols - 1 - k };
             self.set(row, dest_col, Some(Tile::new(val)));
             let sources_len = sources.len();
             for src_c in &sources {
@@ -277,17 +244,17 @@ impl Board {
         moved
     }
 
-    fn compact_column_with_events(&mut self, _start_row: usize, col: usize, row_step: isize, _col_step_unused: isize, out: &mut Vec<MoveEvent>) -> bool {
-        let rows: Vec<usize> = if row_step == 1 { (0..self.size).collect() } else { (0..self.size).rev().collect() };
+    fn compact_column(&mut self, _start_row: usize, col: usize, row_step: isize, _col_step_unused: isize, out: &mut Vec<MoveEvent>) -> bool {
+        let rows: Vec<usize> = if row_step == 1 { (0..self.rows).collect() } else { (0..self.rows).rev().collect() };
         let mut items: Vec<(usize, u32)> = Vec::new();
         for r in &rows { if let Some(t) = self.get(*r, col) { items.push((*r, t.value)); } }
 
         let mut merged: Vec<(Vec<usize>, u32)> = Vec::new();
         let mut i = 0;
         while i < items.len() {
-            if i + 1 < items.len() && items[i].1 == items[i + 1].1 {
+            if i + 1 < items.len() && items[i].1 == items[i + 1].1 && items[i].1 < MAX_TILE {
                 merged.push((vec![items[i].0, items[i + 1].0], items[i].1 * 2));
-                self.score += items[i].1 * 2;
+                self.score = self.score.saturating_add(items[i].1 * 2);
                 i += 2;
             } else {
                 merged.push((vec![items[i].0], items[i].1));
@@ -295,10 +262,10 @@ impl Board {
             }
         }
 
-        for r in 0..self.size { self.set(r, col, None); }
+        for r in 0..self.rows { self.set(r, col, None); }
         let mut moved = false;
-        for (k, (sources, val)) in (0..merged.len()).zip(merged.into_iter()) {
-            let dest_row = if row_step == 1 { k } else { self.size - 1 - k };
+        for (k, (sources, val)) in (0..merged.len()).zip(merged) {
+            let dest_row = if row_step == 1 { k } else { self.rows - 1 - k };
             self.set(dest_row, col, Some(Tile::new(val)));
             let sources_len = sources.len();
             for src_r in &sources {
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use super::spawn::SpawnRules;
+
+pub const DEFAULT_WIN_TARGET: u32 = 2048;
+
+/// Largest number of rows or columns a board may have.
+pub const MAX_DIMENSION: usize = 16;
+
+/// Everything needed to start a game. `seed: None` draws one from entropy.
+/// Missing fields take their defaults when deserialized.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub rows: usize,
+    pub cols: usize,
+    /// Tile value that wins the game, e.g. 512 for kids or 8192 for experts.
+    pub win_target: u32,
+    pub seed: Option<u64>,
+    pub spawn: SpawnRules,
+}
+
+impl GameConfig {
+    pub fn new(size: usize) -> Self {
+        Self::rect(size, size)
+    }
+
+    pub fn rect(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, win_target: DEFAULT_WIN_TARGET, seed: None, spawn: SpawnRules::default() }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let in_range = (1..=MAX_DIMENSION).contains(&self.rows) && (1..=MAX_DIMENSION).contains(&self.cols);
+        let Some(cells) = self.rows.checked_mul(self.cols).filter(|&n| in_range && n >= 2) else {
+            return Err(format!("Gecersiz tahta boyutu: {}x{}", self.rows, self.cols));
+        };
+        self.spawn.validate()?;
+        if self.spawn.initial > cells {
+            return Err("Baslangic tas sayisi tahtaya sigmiyor".to_string());
+        }
+        if self.spawn.per_move > cells {
+            return Err("Hamle basina dogan tas sayisi tahtaya sigmiyor".to_string());
+        }
+        // a target the spawns already reach would count as won before the first
+        // move, and only powers of two can be reached
+        if self.win_target <= self.spawn.max_value() || !self.win_target.is_power_of_two() {
+            return Err(format!("Gecersiz hedef tas: {}", self.win_target));
+        }
+        Ok(())
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self { Self::new(4) }
+}
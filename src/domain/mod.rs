@@ -1,3 +1,6 @@
+pub mod bitboard;
 pub mod board;
+pub mod config;
 pub mod direction;
-
+pub mod rng;
+pub mod spawn;
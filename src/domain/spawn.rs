@@ -0,0 +1,111 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Largest value a tile may spawn with; tiles then still have room to merge
+/// many times before outgrowing a `u32`.
+pub const MAX_SPAWN_VALUE: u32 = 1 << 16;
+
+/// Where new tiles may appear among the empty cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpawnPolicy {
+    #[default]
+    Uniform,
+    /// Cells closer to a corner are proportionally more likely.
+    CornerBiased,
+}
+
+/// How tiles are spawned: a weighted value table, how many tiles appear per
+/// move and at the start, and where they go.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpawnRules {
+    /// `(value, weight)` pairs; the classic game is `[(2, 9), (4, 1)]`.
+    pub values: Vec<(u32, u32)>,
+    pub per_move: usize,
+    pub initial: usize,
+    pub policy: SpawnPolicy,
+}
+
+impl Default for SpawnRules {
+    fn default() -> Self {
+        Self { values: vec![(2, 9), (4, 1)], per_move: 1, initial: 2, policy: SpawnPolicy::Uniform }
+    }
+}
+
+impl SpawnRules {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.total_weight() == 0 {
+            return Err("Dogum tablosunda agirlik yok".to_string());
+        }
+        if let Some(&(v, _)) = self.values.iter().find(|&&(v, _)| !(2..=MAX_SPAWN_VALUE).contains(&v) || !v.is_power_of_two()) {
+            return Err(format!("Gecersiz dogum degeri: {}", v));
+        }
+        if self.initial == 0 {
+            return Err("Baslangic tas sayisi en az 1 olmali".to_string());
+        }
+        Ok(())
+    }
+
+    /// The largest value that can spawn, ignoring zero weights.
+    pub fn max_value(&self) -> u32 {
+        self.values.iter().filter(|&&(_, w)| w > 0).map(|&(v, _)| v).max().unwrap_or(2)
+    }
+
+    /// Summed as `u64`, so no table of `u32` weights can overflow it.
+    fn total_weight(&self) -> u64 {
+        self.values.iter().map(|&(_, w)| w as u64).sum()
+    }
+
+    /// Each value with its probability, skipping zero weights.
+    pub fn probabilities(&self) -> impl Iterator<Item = (u32, f64)> + '_ {
+        let total = self.total_weight();
+        self.values.iter()
+            .filter(|&&(_, w)| w > 0)
+            .map(move |&(v, w)| (v, w as f64 / total as f64))
+    }
+
+    pub fn pick_value(&self, rng: &mut impl Rng) -> u32 {
+        let mut roll = rng.gen_range(0..self.total_weight().max(1));
+        for &(value, weight) in &self.values {
+            if roll < weight as u64 { return value; }
+            roll -= weight as u64;
+        }
+        self.values.first().map_or(2, |&(v, _)| v)
+    }
+
+    /// The chance `pick_position` gives each of `empties` (non-empty).
+    pub fn position_probabilities(&self, empties: &[(usize, usize)], rows: usize, cols: usize) -> Vec<f64> {
+        match self.policy {
+            SpawnPolicy::Uniform => vec![1.0 / empties.len() as f64; empties.len()],
+            SpawnPolicy::CornerBiased => {
+                let weights: Vec<usize> = empties.iter().map(|&pos| corner_weight(pos, rows, cols)).collect();
+                let total = weights.iter().sum::<usize>() as f64;
+                weights.into_iter().map(|w| w as f64 / total).collect()
+            }
+        }
+    }
+
+    /// Picks one of `empties` (non-empty) on a `rows` x `cols` board.
+    pub fn pick_position(&self, empties: &[(usize, usize)], rows: usize, cols: usize, rng: &mut impl Rng) -> (usize, usize) {
+        match self.policy {
+            SpawnPolicy::Uniform => empties[rng.gen_range(0..empties.len())],
+            SpawnPolicy::CornerBiased => {
+                let weights: Vec<usize> = empties.iter().map(|&pos| corner_weight(pos, rows, cols)).collect();
+                let mut roll = rng.gen_range(0..weights.iter().sum::<usize>());
+                for (&pos, &w) in empties.iter().zip(&weights) {
+                    if roll < w { return pos; }
+                    roll -= w;
+                }
+                empties[empties.len() - 1]
+            }
+        }
+    }
+}
+
+/// 1 in the middle of the board, growing by one per step toward the nearest corner.
+fn corner_weight((row, col): (usize, usize), rows: usize, cols: usize) -> usize {
+    let dr = row.min(rows - 1 - row);
+    let dc = col.min(cols - 1 - col);
+    let max = (rows - 1) / 2 + (cols - 1) / 2;
+    1 + max - (dr + dc)
+}
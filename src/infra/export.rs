@@ -0,0 +1,190 @@
+//! Animated GIF and APNG export of a recorded game, drawn with the shared
+//! renderer on a `RasterSurface`.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::application::recording::Recording;
+use crate::domain::board::{Board, MoveOutcome};
+use crate::infra::layout::MOVE_ANIMATION_MS;
+use crate::infra::raster::RasterSurface;
+use crate::infra::surface::{Hud, Renderer};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimationFormat {
+    Gif,
+    Apng,
+}
+
+impl FromStr for AnimationFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "gif" => Ok(AnimationFormat::Gif),
+            "apng" | "png" => Ok(AnimationFormat::Apng),
+            _ => Err(format!("Bilinmeyen animasyon bicimi: {}", s)),
+        }
+    }
+}
+
+/// Largest width or height of an exported image; a frame is held in memory
+/// as RGBA, so this keeps one under 64 MB.
+pub const MAX_EXPORT_SIZE: u32 = 4096;
+
+/// Most frames one export draws, so a long game or slow animation cannot run forever.
+pub const MAX_EXPORT_FRAMES: usize = 50_000;
+
+/// Longest move animation or final hold; also within what a GIF delay can store.
+const MAX_DELAY_MS: f64 = 60_000.0;
+
+/// Image size in pixels, frames per second while tiles move, how long a
+/// move animates (as in the browser) and how long the last frame is held.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExportOptions {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    pub move_ms: f64,
+    pub hold_ms: f64,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self { width: 420, height: 520, fps: 30, move_ms: MOVE_ANIMATION_MS, hold_ms: 1500.0 }
+    }
+}
+
+impl ExportOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_EXPORT_SIZE).contains(&self.width) || !(1..=MAX_EXPORT_SIZE).contains(&self.height) {
+            return Err(format!("Gecersiz goruntu boyutu: {}x{}", self.width, self.height));
+        }
+        if self.fps == 0 || self.fps > 100 {
+            return Err(format!("Kare hizi 1 ile 100 arasinda olmali: {}", self.fps));
+        }
+        for ms in [self.move_ms, self.hold_ms] {
+            if !(0.0..=MAX_DELAY_MS).contains(&ms) {
+                return Err(format!("Sure 0 ile {} ms arasinda olmali: {}", MAX_DELAY_MS, ms));
+            }
+        }
+        Ok(())
+    }
+
+    fn frames_per_move(&self) -> usize { (self.move_ms / self.frame_ms()).ceil().max(1.0) as usize }
+
+    fn frame_ms(&self) -> f64 { 1000.0 / self.fps as f64 }
+}
+
+/// What one frame shows; frames are planned up front (APNG needs the count)
+/// and drawn one at a time so long games don't sit in memory.
+struct PlannedFrame<'a> {
+    board: &'a Board,
+    motion: Option<(&'a MoveOutcome, f64)>,
+    hud: Hud,
+    delay_ms: f64,
+}
+
+fn plan<'a>(recording: &'a Recording, options: &ExportOptions) -> Vec<PlannedFrame<'a>> {
+    let frame_ms = options.frame_ms();
+    let per_move = options.frames_per_move();
+    let mut best = recording.initial.score;
+    let hud = |board: &Board, best: u32| Hud { score: board.score, best_score: best, ..Hud::default() };
+
+    let mut frames = vec![PlannedFrame { board: &recording.initial, motion: None, hud: hud(&recording.initial, best), delay_ms: frame_ms }];
+    for step in &recording.steps {
+        best = best.max(step.board.score);
+        if step.direction.is_some() {
+            for k in 1..=per_move {
+                let progress = k as f64 / per_move as f64;
+                frames.push(PlannedFrame { board: &step.board, motion: Some((&step.outcome, progress)), hud: hud(&step.board, best), delay_ms: frame_ms });
+            }
+        }
+        // settle on the static board, as the browser does once an animation ends
+        frames.push(PlannedFrame { board: &step.board, motion: None, hud: hud(&step.board, best), delay_ms: frame_ms });
+    }
+    if let Some(last) = frames.last_mut() { last.delay_ms = options.hold_ms.max(frame_ms); }
+    frames
+}
+
+fn draw(frame: &PlannedFrame, options: &ExportOptions) -> Vec<u8> {
+    let mut surface = RasterSurface::new(options.width, options.height);
+    match frame.motion {
+        Some((outcome, progress)) => surface.render_frame(frame.board, &frame.hud, outcome, progress),
+        None => surface.render(frame.board, &frame.hud),
+    }
+    surface.into_pixels()
+}
+
+pub fn export_recording(recording: &Recording, format: AnimationFormat, options: &ExportOptions) -> Result<Vec<u8>, String> {
+    options.validate()?;
+    // the first frame, one settled frame per step and the motion of each move
+    let moves = recording.steps.iter().filter(|s| s.direction.is_some()).count();
+    let count = moves.checked_mul(options.frames_per_move()).and_then(|n| n.checked_add(recording.steps.len() + 1));
+    if count.is_none_or(|n| n > MAX_EXPORT_FRAMES) {
+        return Err(format!("Kayit en fazla {} kare olabilir", MAX_EXPORT_FRAMES));
+    }
+    let frames = plan(recording, options);
+    match format {
+        AnimationFormat::Gif => encode_gif(&frames, options),
+        AnimationFormat::Apng => encode_apng(&frames, options),
+    }
+}
+
+/// Each frame carries its own exact palette; the renderer only uses a few
+/// dozen colours, so quantising is a fallback for unusual surfaces.
+fn encode_gif(frames: &[PlannedFrame], options: &ExportOptions) -> Result<Vec<u8>, String> {
+    let (w, h) = (options.width as u16, options.height as u16);
+    let mut out = Vec::new();
+    {
+        let mut encoder = gif::Encoder::new(&mut out, w, h, &[]).map_err(|e| format!("GIF yazilamadi: {}", e))?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(|e| format!("GIF yazilamadi: {}", e))?;
+        for planned in frames {
+            let mut rgba = draw(planned, options);
+            let mut frame = indexed_frame(&rgba, w, h).unwrap_or_else(|| gif::Frame::from_rgba_speed(w, h, &mut rgba, 10));
+            frame.delay = (planned.delay_ms / 10.0).round().clamp(1.0, u16::MAX as f64) as u16;
+            encoder.write_frame(&frame).map_err(|e| format!("GIF yazilamadi: {}", e))?;
+        }
+    }
+    Ok(out)
+}
+
+/// The frame with a palette of exactly its colours, or `None` past 256 of them.
+fn indexed_frame(rgba: &[u8], width: u16, height: u16) -> Option<gif::Frame<'static>> {
+    let mut palette: Vec<u8> = Vec::new();
+    let mut index: HashMap<[u8; 3], u8> = HashMap::new();
+    let mut buffer = Vec::with_capacity(rgba.len() / 4);
+    for px in rgba.chunks_exact(4) {
+        let color = [px[0], px[1], px[2]];
+        let i = match index.get(&color) {
+            Some(&i) => i,
+            None => {
+                let i = u8::try_from(index.len()).ok()?;
+                index.insert(color, i);
+                palette.extend_from_slice(&color);
+                i
+            }
+        };
+        buffer.push(i);
+    }
+    Some(gif::Frame { width, height, buffer: Cow::Owned(buffer), palette: Some(palette), ..gif::Frame::default() })
+}
+
+fn encode_apng(frames: &[PlannedFrame], options: &ExportOptions) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, options.width, options.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(frames.len() as u32, 0).map_err(|e| format!("APNG yazilamadi: {}", e))?;
+        let mut writer = encoder.write_header().map_err(|e| format!("APNG yazilamadi: {}", e))?;
+        for planned in frames {
+            let delay = planned.delay_ms.round().clamp(1.0, u16::MAX as f64) as u16;
+            writer.set_frame_delay(delay, 1000).map_err(|e| format!("APNG yazilamadi: {}", e))?;
+            writer.write_image_data(&draw(planned, options)).map_err(|e| format!("APNG yazilamadi: {}", e))?;
+        }
+        writer.finish().map_err(|e| format!("APNG yazilamadi: {}", e))?;
+    }
+    Ok(out)
+}
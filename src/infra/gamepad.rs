@@ -0,0 +1,136 @@
+//! Controller input: turns polled gamepad states into game actions, with
+//! a dead zone for the stick and key-style repeat for held directions.
+//! Independent of the browser; `wasm_bindings` feeds it the Gamepad API.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::direction::Direction;
+use crate::infra::input::InputAction;
+
+/// Which buttons and axes do what. Button and axis numbers follow the
+/// browser's "standard" gamepad layout. Missing fields take their defaults
+/// when deserialized.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GamepadMapping {
+    /// `(button, action)` pairs; the default has the D-pad move, B undo,
+    /// X redo, Y restart, A continue after a win and Start pause.
+    pub buttons: Vec<(usize, InputAction)>,
+    /// Horizontal and vertical stick axes; `None` turns the stick off.
+    pub stick: Option<(usize, usize)>,
+    /// Stick deflection, from 0 to 1, below which it counts as centred.
+    pub dead_zone: f64,
+    /// A held direction moves again after `repeat_delay_ms`, then every
+    /// `repeat_interval_ms`; an interval of 0 turns repeat off.
+    pub repeat_delay_ms: f64,
+    pub repeat_interval_ms: f64,
+}
+
+impl Default for GamepadMapping {
+    fn default() -> Self {
+        Self {
+            buttons: vec![
+                (12, InputAction::Move(Direction::Up)),
+                (13, InputAction::Move(Direction::Down)),
+                (14, InputAction::Move(Direction::Left)),
+                (15, InputAction::Move(Direction::Right)),
+                (0, InputAction::Continue),
+                (1, InputAction::Undo),
+                (2, InputAction::Redo),
+                (3, InputAction::Restart),
+                (9, InputAction::Pause),
+            ],
+            stick: Some((0, 1)),
+            dead_zone: 0.5,
+            repeat_delay_ms: 300.0,
+            repeat_interval_ms: 150.0,
+        }
+    }
+}
+
+impl GamepadMapping {
+    pub fn validate(&self) -> Result<(), String> {
+        // with no dead zone a centred stick would read as pushed left
+        if !(self.dead_zone > 0.0 && self.dead_zone < 1.0) {
+            return Err(format!("Olu bolge 0'dan buyuk, 1'den kucuk olmali: {}", self.dead_zone));
+        }
+        if !(self.repeat_delay_ms >= 0.0 && self.repeat_interval_ms >= 0.0) {
+            return Err("Tekrar sureleri negatif olamaz".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// One poll of a controller: which buttons are down and where the axes are.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PadState {
+    pub buttons: Vec<bool>,
+    pub axes: Vec<f64>,
+}
+
+/// Remembers what was held at the last poll, so presses fire once and
+/// held directions repeat on schedule.
+#[derive(Clone, Debug, Default)]
+pub struct GamepadInput {
+    mapping: GamepadMapping,
+    pressed: Vec<bool>,
+    held: Option<Direction>,
+    next_repeat_ms: f64,
+}
+
+impl GamepadInput {
+    pub fn new(mapping: GamepadMapping) -> Self {
+        Self { mapping, ..Self::default() }
+    }
+
+    pub fn mapping(&self) -> &GamepadMapping { &self.mapping }
+
+    pub fn set_mapping(&mut self, mapping: GamepadMapping) { *self = Self::new(mapping); }
+
+    /// The actions `state` triggers at `now_ms`, directions first.
+    pub fn update(&mut self, state: &PadState, now_ms: f64) -> Vec<InputAction> {
+        let mut actions = Vec::new();
+
+        let direction = self.direction(state);
+        match direction {
+            Some(d) if self.held != Some(d) => {
+                actions.push(InputAction::Move(d));
+                self.next_repeat_ms = now_ms + self.mapping.repeat_delay_ms;
+            }
+            Some(d) if self.mapping.repeat_interval_ms > 0.0 && now_ms >= self.next_repeat_ms => {
+                actions.push(InputAction::Move(d));
+                self.next_repeat_ms = now_ms + self.mapping.repeat_interval_ms;
+            }
+            _ => {}
+        }
+        self.held = direction;
+
+        for &(button, action) in &self.mapping.buttons {
+            let down = state.buttons.get(button).copied().unwrap_or(false);
+            let was_down = self.pressed.get(button).copied().unwrap_or(false);
+            if down && !was_down && !matches!(action, InputAction::Move(_)) { actions.push(action); }
+        }
+        self.pressed = state.buttons.clone();
+        actions
+    }
+
+    /// The direction held on a mapped button, or else on the stick.
+    fn direction(&self, state: &PadState) -> Option<Direction> {
+        let button = self.mapping.buttons.iter().find_map(|&(button, action)| match action {
+            InputAction::Move(d) if state.buttons.get(button).copied().unwrap_or(false) => Some(d),
+            _ => None,
+        });
+        button.or_else(|| {
+            let (x_axis, y_axis) = self.mapping.stick?;
+            let x = state.axes.get(x_axis).copied().unwrap_or(0.0);
+            let y = state.axes.get(y_axis).copied().unwrap_or(0.0);
+            if x.abs().max(y.abs()) < self.mapping.dead_zone { return None; }
+            Some(match (x.abs() >= y.abs(), x > 0.0, y > 0.0) {
+                (true, true, _) => Direction::Right,
+                (true, false, _) => Direction::Left,
+                (false, _, true) => Direction::Down,
+                (false, _, false) => Direction::Up,
+            })
+        })
+    }
+}
@@ -0,0 +1,99 @@
+//! Swipe detection from raw pointer events, independent of the browser so
+//! the thresholds can be tested natively. Positions are in CSS pixels with
+//! `y` growing downwards, times in milliseconds.
+
+use crate::domain::direction::Direction;
+
+/// When a pointer gesture counts as a swipe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwipeConfig {
+    /// Shortest travel, in pixels, from press to release.
+    pub min_distance: f64,
+    /// Slowest average speed, in pixels per millisecond.
+    pub min_velocity: f64,
+    /// Largest angle, in degrees, between the swipe and the nearest axis.
+    pub angle_tolerance: f64,
+}
+
+impl Default for SwipeConfig {
+    fn default() -> Self {
+        Self { min_distance: 30.0, min_velocity: 0.2, angle_tolerance: 30.0 }
+    }
+}
+
+impl SwipeConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.min_distance >= 0.0 && self.min_velocity >= 0.0) {
+            return Err("Kaydirma esikleri negatif olamaz".to_string());
+        }
+        if !(0.0..=45.0).contains(&self.angle_tolerance) {
+            return Err(format!("Aci toleransi 0 ile 45 derece arasinda olmali: {}", self.angle_tolerance));
+        }
+        Ok(())
+    }
+
+    /// The direction of a stroke of `(dx, dy)` over `duration_ms`, if it is a swipe.
+    pub fn classify(&self, dx: f64, dy: f64, duration_ms: f64) -> Option<Direction> {
+        let distance = dx.hypot(dy);
+        if distance < self.min_distance || distance == 0.0 { return None; }
+        if distance / duration_ms.max(1.0) < self.min_velocity { return None; }
+        let off_axis = dx.abs().min(dy.abs()).atan2(dx.abs().max(dy.abs())).to_degrees();
+        if off_axis > self.angle_tolerance { return None; }
+        Some(match (dx.abs() >= dy.abs(), dx > 0.0, dy > 0.0) {
+            (true, true, _) => Direction::Right,
+            (true, false, _) => Direction::Left,
+            (false, _, true) => Direction::Down,
+            (false, _, false) => Direction::Up,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Press {
+    pointer: i32,
+    x: f64,
+    y: f64,
+    t_ms: f64,
+}
+
+/// Tracks the pointers on the board. A second pointer going down cancels
+/// the gesture, and a new one only starts once every pointer is lifted.
+#[derive(Clone, Debug, Default)]
+pub struct SwipeDetector {
+    config: SwipeConfig,
+    down: Vec<i32>,
+    press: Option<Press>,
+}
+
+impl SwipeDetector {
+    pub fn new(config: SwipeConfig) -> Self {
+        Self { config, ..Self::default() }
+    }
+
+    pub fn config(&self) -> SwipeConfig { self.config }
+
+    pub fn set_config(&mut self, config: SwipeConfig) { self.config = config; }
+
+    pub fn pointer_down(&mut self, pointer: i32, x: f64, y: f64, t_ms: f64) {
+        if !self.down.contains(&pointer) { self.down.push(pointer); }
+        self.press = (self.down.len() == 1).then_some(Press { pointer, x, y, t_ms });
+    }
+
+    /// The swipe completed by lifting `pointer`, if any.
+    pub fn pointer_up(&mut self, pointer: i32, x: f64, y: f64, t_ms: f64) -> Option<Direction> {
+        self.down.retain(|&p| p != pointer);
+        let press = self.press.filter(|p| p.pointer == pointer)?;
+        self.press = None;
+        self.config.classify(x - press.x, y - press.y, t_ms - press.t_ms)
+    }
+
+    /// The pointer left without a release (the browser took it over, the
+    /// window lost focus, ...); its gesture is dropped.
+    pub fn pointer_cancel(&mut self, pointer: i32) {
+        self.down.retain(|&p| p != pointer);
+        if self.press.is_some_and(|p| p.pointer == pointer) { self.press = None; }
+    }
+
+    /// True while a single pointer is down and may still become a swipe.
+    pub fn is_tracking(&self) -> bool { self.press.is_some() }
+}
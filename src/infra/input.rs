@@ -0,0 +1,171 @@
+//! What the player can ask for, and which keys ask for it. Keys are
+//! `KeyboardEvent.code` values ("KeyW", "ArrowUp", ...), so bindings stay on
+//! the same physical keys whatever the keyboard layout.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::replay::{parse_named, ReplayAction};
+use crate::domain::direction::Direction;
+
+/// A game action, named in JS and in saved bindings by `as_str`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum InputAction {
+    Move(Direction),
+    Restart,
+    Undo,
+    Redo,
+    /// Keep playing after reaching the target.
+    Continue,
+    /// Ask the solver for a move.
+    Hint,
+    /// Freeze the game until paused again.
+    Pause,
+    /// Let the solver play.
+    Autoplay,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 11] = [
+        InputAction::Move(Direction::Up),
+        InputAction::Move(Direction::Down),
+        InputAction::Move(Direction::Left),
+        InputAction::Move(Direction::Right),
+        InputAction::Restart,
+        InputAction::Undo,
+        InputAction::Redo,
+        InputAction::Continue,
+        InputAction::Hint,
+        InputAction::Pause,
+        InputAction::Autoplay,
+    ];
+
+    /// Actions that change the game share their names with replay steps.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InputAction::Move(direction) => ReplayAction::Move(direction).as_str(),
+            InputAction::Undo => ReplayAction::Undo.as_str(),
+            InputAction::Redo => ReplayAction::Redo.as_str(),
+            InputAction::Continue => ReplayAction::KeepPlaying.as_str(),
+            InputAction::Restart => "restart",
+            InputAction::Hint => "hint",
+            InputAction::Pause => "pause",
+            InputAction::Autoplay => "autoplay",
+        }
+    }
+}
+
+impl InputAction {
+    /// Whether holding the key plays the action again; one-shot and toggle
+    /// actions ignore key repeat so holding P does not flicker the pause.
+    pub fn repeats(self) -> bool {
+        matches!(self, InputAction::Move(_) | InputAction::Undo | InputAction::Redo)
+    }
+}
+
+impl From<InputAction> for String {
+    fn from(action: InputAction) -> Self { action.as_str().to_string() }
+}
+
+impl TryFrom<String> for InputAction {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::str::FromStr for InputAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_named(&InputAction::ALL, InputAction::as_str, s)
+    }
+}
+
+/// Key code to action. A key does one thing; an action may have any number
+/// of keys, or none.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InputMap {
+    bindings: BTreeMap<String, InputAction>,
+}
+
+impl Default for InputMap {
+    /// Arrows, WASD and HJKL move; R restarts, U or Z undoes, Y redoes,
+    /// C or Enter continues, I hints, P or Escape pauses, O autoplays.
+    fn default() -> Self {
+        let defaults = [
+            (["ArrowUp", "KeyW", "KeyK"].as_slice(), InputAction::Move(Direction::Up)),
+            (&["ArrowDown", "KeyS", "KeyJ"], InputAction::Move(Direction::Down)),
+            (&["ArrowLeft", "KeyA", "KeyH"], InputAction::Move(Direction::Left)),
+            (&["ArrowRight", "KeyD", "KeyL"], InputAction::Move(Direction::Right)),
+            (&["KeyR"], InputAction::Restart),
+            (&["KeyU", "KeyZ"], InputAction::Undo),
+            (&["KeyY"], InputAction::Redo),
+            (&["KeyC", "Enter", "NumpadEnter"], InputAction::Continue),
+            (&["KeyI"], InputAction::Hint),
+            (&["KeyP", "Escape"], InputAction::Pause),
+            (&["KeyO"], InputAction::Autoplay),
+        ];
+        let bindings = defaults.iter()
+            .flat_map(|(codes, action)| codes.iter().map(move |code| (code.to_string(), *action)))
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl InputMap {
+    /// No keys bound at all.
+    pub fn empty() -> Self {
+        Self { bindings: BTreeMap::new() }
+    }
+
+    pub fn action(&self, code: &str) -> Option<InputAction> { self.bindings.get(code).copied() }
+
+    /// Keys bound to `action`, in code order.
+    pub fn keys(&self, action: InputAction) -> Vec<&str> {
+        self.bindings.iter().filter(|(_, a)| **a == action).map(|(code, _)| code.as_str()).collect()
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (&str, InputAction)> {
+        self.bindings.iter().map(|(code, action)| (code.as_str(), *action))
+    }
+
+    /// The action other than `action` that already uses `code`.
+    pub fn conflict(&self, code: &str, action: InputAction) -> Option<InputAction> {
+        self.action(code).filter(|a| *a != action)
+    }
+
+    /// Binds `code` to `action`, refusing a key that already does something else.
+    pub fn bind(&mut self, code: &str, action: InputAction) -> Result<(), String> {
+        if code.is_empty() {
+            return Err("Bos tus kodu".to_string());
+        }
+        if let Some(other) = self.conflict(code, action) {
+            return Err(format!("{} tusu zaten '{}' icin kullaniliyor", code, other.as_str()));
+        }
+        self.bindings.insert(code.to_string(), action);
+        Ok(())
+    }
+
+    /// Binds `code` to `action` even if it did something else; returns what it did.
+    pub fn rebind(&mut self, code: &str, action: InputAction) -> Option<InputAction> {
+        self.bindings.insert(code.to_string(), action).filter(|a| *a != action)
+    }
+
+    pub fn unbind(&mut self, code: &str) -> Option<InputAction> { self.bindings.remove(code) }
+
+    /// Removes every key of `action`.
+    pub fn clear(&mut self, action: InputAction) { self.bindings.retain(|_, a| *a != action); }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Tus atamalari okunamadi: {}", e))
+    }
+}
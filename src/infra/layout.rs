@@ -0,0 +1,137 @@
+//! Where things go on screen and how they move, independent of any drawing
+//! backend. Units are whatever the surface uses (canvas pixels, SVG user
+//! units, image pixels).
+
+use std::collections::HashSet;
+
+use crate::domain::board::{Board, MoveOutcome};
+
+pub const PAD: f64 = 12.0;
+/// How long a move animates unless configured otherwise, in milliseconds.
+pub const MOVE_ANIMATION_MS: f64 = 140.0;
+/// Height kept free under the grid for the score line.
+pub const SCORE_STRIP: f64 = 40.0;
+/// Share of an animation spent on the merge pop and spawn grow at its end.
+const POP_START: f64 = 0.8;
+const POP_SCALE: f64 = 0.15;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub w: f64,
+    pub h: f64,
+}
+
+impl Rect {
+    pub fn center(&self) -> (f64, f64) { (self.x + self.w / 2.0, self.y + self.h / 2.0) }
+}
+
+/// Grid geometry for a board drawn on a `width` x `height` surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layout {
+    pub rows: usize,
+    pub cols: usize,
+    pub width: f64,
+    pub height: f64,
+    pub pad: f64,
+    pub tile_size: f64,
+}
+
+impl Layout {
+    /// Largest square tile that fits the board's columns across and rows down.
+    pub fn new(rows: usize, cols: usize, width: f64, height: f64) -> Self {
+        let across = (width - PAD * (cols as f64 + 1.0)) / cols as f64;
+        let down = (height - SCORE_STRIP - PAD * (rows as f64 + 1.0)) / rows as f64;
+        Self { rows, cols, width, height, pad: PAD, tile_size: across.min(down) }
+    }
+
+    pub fn for_board(board: &Board, width: f64, height: f64) -> Self {
+        Self::new(board.rows, board.cols, width, height)
+    }
+
+    /// Cell at a possibly fractional grid position, grown or shrunk about its centre.
+    pub fn cell(&self, grid_x: f64, grid_y: f64, scale: f64) -> Rect {
+        let x = self.pad + grid_x * (self.tile_size + self.pad);
+        let y = self.pad + grid_y * (self.tile_size + self.pad);
+        let s = self.tile_size * scale;
+        Rect { x: x + (self.tile_size - s) / 2.0, y: y + (self.tile_size - s) / 2.0, w: s, h: s }
+    }
+
+    /// The grid with its outer padding, e.g. for the win overlay.
+    pub fn grid(&self) -> Rect {
+        Rect {
+            x: 0.0,
+            y: 0.0,
+            w: self.pad + self.cols as f64 * (self.tile_size + self.pad),
+            h: self.pad + self.rows as f64 * (self.tile_size + self.pad),
+        }
+    }
+
+    /// Font size for a tile's value at `scale`.
+    pub fn font_size(&self, scale: f64) -> f64 { (self.tile_size * 0.5 * scale).floor() }
+}
+
+pub fn ease_out_cubic(t: f64) -> f64 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// A tile to draw this frame at a grid position, possibly between cells.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sprite {
+    pub value: u32,
+    pub grid_x: f64,
+    pub grid_y: f64,
+    pub scale: f64,
+}
+
+/// Every tile of a settled board.
+pub fn static_sprites(board: &Board) -> Vec<Sprite> {
+    let mut sprites = Vec::new();
+    for r in 0..board.rows {
+        for c in 0..board.cols {
+            if let Some(tile) = board.get(r, c) {
+                sprites.push(Sprite { value: tile.value, grid_x: c as f64, grid_y: r as f64, scale: 1.0 });
+            }
+        }
+    }
+    sprites
+}
+
+/// Tiles of `board` (already after the move) partway through animating
+/// `outcome`, in drawing order: resting tiles, sliding tiles, then merge
+/// results and spawns popping in near the end.
+pub fn animated_sprites(board: &Board, outcome: &MoveOutcome, progress: f64) -> Vec<Sprite> {
+    let p = progress.clamp(0.0, 1.0);
+    let ep = ease_out_cubic(p);
+
+    // destinations are drawn by their movers, spawns only once the slide is done
+    let mut hidden: HashSet<(usize, usize)> = HashSet::new();
+    for m in &outcome.events { hidden.insert((m.to_row, m.to_col)); }
+    for spawn in &outcome.spawned { hidden.insert((spawn.row, spawn.col)); }
+
+    let mut sprites: Vec<Sprite> = static_sprites(board)
+        .into_iter()
+        .filter(|s| !hidden.contains(&(s.grid_y as usize, s.grid_x as usize)))
+        .collect();
+
+    let pop = if p > POP_START { (p - POP_START) / (1.0 - POP_START) } else { 0.0 };
+    for m in &outcome.events {
+        let (fx, fy) = (m.from_col as f64, m.from_row as f64);
+        let (tx, ty) = (m.to_col as f64, m.to_row as f64);
+        let scale = if m.merged_into_value.is_some() { 1.0 + POP_SCALE * pop } else { 1.0 };
+        sprites.push(Sprite { value: m.value, grid_x: fx + (tx - fx) * ep, grid_y: fy + (ty - fy) * ep, scale });
+    }
+
+    if p >= POP_START {
+        for m in &outcome.events {
+            if let Some(value) = m.merged_into_value {
+                sprites.push(Sprite { value, grid_x: m.to_col as f64, grid_y: m.to_row as f64, scale: 1.0 + POP_SCALE * pop });
+            }
+        }
+        for spawn in &outcome.spawned {
+            sprites.push(Sprite { value: spawn.value, grid_x: spawn.col as f64, grid_y: spawn.row as f64, scale: pop });
+        }
+    }
+    sprites
+}
@@ -1,3 +1,13 @@
+pub mod export;
+pub mod gamepad;
+pub mod gesture;
+pub mod input;
+pub mod layout;
+pub mod move_queue;
+pub mod palette;
+pub mod raster;
 pub mod render2d;
+pub mod storage;
+pub mod surface;
+pub mod svg;
 pub mod wasm_bindings;
-
@@ -0,0 +1,49 @@
+//! Directions pressed while a move is still animating, played in order
+//! once it ends instead of being dropped.
+
+use std::collections::VecDeque;
+
+use crate::domain::direction::Direction;
+
+/// Moves buffered before further ones are refused.
+pub const DEFAULT_QUEUE_CAPACITY: usize = 4;
+
+#[derive(Clone, Debug)]
+pub struct MoveQueue {
+    capacity: usize,
+    moves: VecDeque<Direction>,
+}
+
+impl Default for MoveQueue {
+    fn default() -> Self { Self::new(DEFAULT_QUEUE_CAPACITY) }
+}
+
+impl MoveQueue {
+    /// A capacity of 0 buffers nothing, so input during animations is dropped.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, moves: VecDeque::with_capacity(capacity) }
+    }
+
+    pub fn capacity(&self) -> usize { self.capacity }
+
+    /// Shrinking drops the newest moves that no longer fit.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.moves.truncate(capacity);
+    }
+
+    /// Buffers `direction`; false when the queue is full and it was dropped.
+    pub fn push(&mut self, direction: Direction) -> bool {
+        if self.moves.len() >= self.capacity { return false; }
+        self.moves.push_back(direction);
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<Direction> { self.moves.pop_front() }
+
+    pub fn clear(&mut self) { self.moves.clear(); }
+
+    pub fn len(&self) -> usize { self.moves.len() }
+
+    pub fn is_empty(&self) -> bool { self.moves.is_empty() }
+}
@@ -0,0 +1,35 @@
+//! Colours shared by every front end, as CSS hex strings.
+
+pub const BACKGROUND: &str = "#faf8ef";
+pub const EMPTY_CELL: &str = "#bbada0";
+pub const TEXT: &str = "#776e65";
+pub const TEXT_LIGHT: &str = "#f9f6f2";
+/// Drawn at half opacity over the grid once the target is reached.
+pub const WIN_OVERLAY: &str = "#edc22e";
+
+pub fn tile_color(value: u32) -> &'static str {
+    match value {
+        2 => "#eee4da",
+        4 => "#ede0c8",
+        8 => "#f2b179",
+        16 => "#f59563",
+        32 => "#f67c5f",
+        64 => "#f65e3b",
+        128 => "#edcf72",
+        256 => "#edcc61",
+        512 => "#edc850",
+        1024 => "#edc53f",
+        2048 => "#edc22e",
+        _ => "#3c3a32",
+    }
+}
+
+pub fn tile_text_color(value: u32) -> &'static str {
+    if value <= 4 { TEXT } else { TEXT_LIGHT }
+}
+
+/// `"#rrggbb"` as its three channels; anything malformed is black.
+pub fn rgb(hex: &str) -> (u8, u8, u8) {
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok()).unwrap_or(0);
+    (channel(1), channel(3), channel(5))
+}
@@ -0,0 +1,136 @@
+//! A CPU-only RGBA surface for image export. Rectangles snap to whole
+//! pixels and text uses a built-in 5x7 bitmap font, so frames only contain
+//! the palette colours and encode well as GIF.
+
+use crate::infra::layout::Rect;
+use crate::infra::palette;
+use crate::infra::surface::{DrawSurface, TextAlign, TextStyle};
+
+const GLYPH_W: usize = 5;
+const GLYPH_H: usize = 7;
+/// Font units per em: a 16px font draws glyphs 1.6px per unit, 11.2px tall.
+const UNITS_PER_EM: f64 = 10.0;
+
+/// Rows of a glyph, most significant of the low five bits on the left.
+/// Lowercase and Turkish letters fall back to their plain capitals.
+fn glyph(c: char) -> [u8; GLYPH_H] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' | 'ç' | 'Ç' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' | 'ğ' | 'Ğ' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' | 'ı' | 'İ' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' | 'ö' | 'Ö' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' | 'ş' | 'Ş' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' | 'ü' | 'Ü' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        ':' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        '!' => [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100],
+        '·' => [0b00000, 0b00000, 0b00000, 0b01100, 0b01100, 0b00000, 0b00000],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        _ => [0; GLYPH_H],
+    }
+}
+
+pub struct RasterSurface {
+    width: u32,
+    height: u32,
+    /// Row-major RGBA, fully opaque.
+    pixels: Vec<u8>,
+}
+
+impl RasterSurface {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, pixels: vec![255; width as usize * height as usize * 4] }
+    }
+
+    pub fn width(&self) -> u32 { self.width }
+
+    pub fn height(&self) -> u32 { self.height }
+
+    pub fn pixels(&self) -> &[u8] { &self.pixels }
+
+    pub fn into_pixels(self) -> Vec<u8> { self.pixels }
+
+    /// Blends `color` over the pixels whose centres fall inside `(x0, y0)..(x1, y1)`.
+    fn blend(&mut self, x0: f64, y0: f64, x1: f64, y1: f64, (r, g, b): (u8, u8, u8), opacity: f64) {
+        let clamp_x = |v: f64| v.round().clamp(0.0, self.width as f64) as usize;
+        let clamp_y = |v: f64| v.round().clamp(0.0, self.height as f64) as usize;
+        let (x0, x1, y0, y1) = (clamp_x(x0), clamp_x(x1), clamp_y(y0), clamp_y(y1));
+        let a = opacity.clamp(0.0, 1.0);
+        for y in y0..y1 {
+            let row = y * self.width as usize;
+            for x in x0..x1 {
+                let px = &mut self.pixels[(row + x) * 4..(row + x) * 4 + 3];
+                for (dst, src) in px.iter_mut().zip([r, g, b]) {
+                    *dst = (src as f64 * a + *dst as f64 * (1.0 - a)).round() as u8;
+                }
+            }
+        }
+    }
+}
+
+impl DrawSurface for RasterSurface {
+    fn size(&self) -> (f64, f64) { (self.width as f64, self.height as f64) }
+
+    fn fill_rect(&mut self, rect: Rect, color: &str, opacity: f64) {
+        self.blend(rect.x, rect.y, rect.x + rect.w, rect.y + rect.h, palette::rgb(color), opacity);
+    }
+
+    fn text(&mut self, text: &str, x: f64, y: f64, style: TextStyle) {
+        let unit = style.size / UNITS_PER_EM;
+        let advance = (GLYPH_W + 1) as f64 * unit;
+        let count = text.chars().count() as f64;
+        let width = count * advance - unit;
+        let left = match style.align {
+            TextAlign::Left => x,
+            TextAlign::Center => x - width / 2.0,
+            TextAlign::Right => x - width,
+        };
+        let top = if style.middle { y - GLYPH_H as f64 * unit / 2.0 } else { y - GLYPH_H as f64 * unit };
+        let color = palette::rgb(style.color);
+        // bold doubles every stroke one unit to the right
+        let stroke = if style.bold { 2.0 } else { 1.0 };
+
+        for (i, c) in text.chars().enumerate() {
+            let gx = left + i as f64 * advance;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..GLYPH_W {
+                    if bits & (1 << (GLYPH_W - 1 - col)) != 0 {
+                        let px = gx + col as f64 * unit;
+                        let py = top + row as f64 * unit;
+                        self.blend(px, py, px + unit * stroke, py + unit, color, 1.0);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use web_sys::Storage;
+
+use crate::application::snapshot::GameSnapshot;
+use crate::infra::input::InputMap;
+
+/// `window.localStorage` wrapper. Keys are prefixed with a namespace so several
+/// boards on one page keep separate saves. When storage is unavailable (private
+/// mode, disabled cookies, quota) every call quietly becomes a no-op.
+pub struct GameStorage {
+    storage: Option<Storage>,
+    namespace: String,
+}
+
+impl GameStorage {
+    pub fn new(namespace: &str) -> Self {
+        let storage = web_sys::window().and_then(|w| w.local_storage().ok().flatten());
+        Self { storage, namespace: namespace.to_string() }
+    }
+
+    fn key(&self, name: &str) -> String { format!("game_2048:{}:{}", self.namespace, name) }
+
+    fn get(&self, name: &str) -> Option<String> {
+        self.storage.as_ref()?.get_item(&self.key(name)).ok().flatten()
+    }
+
+    fn set(&self, name: &str, value: &str) {
+        if let Some(storage) = &self.storage {
+            let _ = storage.set_item(&self.key(name), value);
+        }
+    }
+
+    /// A save that fails to parse or migrate is treated as missing.
+    pub fn load_game(&self) -> Option<GameSnapshot> {
+        GameSnapshot::from_json(&self.get("game")?).ok()
+    }
+
+    pub fn save_game(&self, snapshot: &GameSnapshot) {
+        if let Ok(json) = snapshot.to_json() {
+            self.set("game", &json);
+        }
+    }
+
+    pub fn load_best_score(&self) -> u32 {
+        self.get("best").and_then(|v| v.parse().ok()).unwrap_or(0)
+    }
+
+    pub fn save_best_score(&self, best: u32) {
+        self.set("best", &best.to_string());
+    }
+
+    /// Saved key bindings; `None` when there are none or they fail to parse.
+    pub fn load_input_map(&self) -> Option<InputMap> {
+        InputMap::from_json(&self.get("keys")?).ok()
+    }
+
+    pub fn save_input_map(&self, map: &InputMap) {
+        if let Ok(json) = map.to_json() {
+            self.set("keys", &json);
+        }
+    }
+}
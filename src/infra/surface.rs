@@ -0,0 +1,135 @@
+//! Drawing on any backend: a `DrawSurface` only fills rectangles and writes
+//! text; `Renderer` turns a board into those calls using the shared layout,
+//! so every backend draws the same picture.
+
+use crate::application::game_service::GameService;
+use crate::domain::board::{Board, MoveOutcome};
+use crate::infra::layout::{animated_sprites, static_sprites, Layout, Rect, Sprite};
+use crate::infra::palette;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// How a line of text is set; `y` is its vertical middle when `middle` is
+/// true and its baseline otherwise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextStyle<'a> {
+    pub size: f64,
+    pub bold: bool,
+    pub color: &'a str,
+    pub align: TextAlign,
+    pub middle: bool,
+}
+
+/// Primitive drawing operations a backend provides. Colours are CSS hex
+/// strings from `palette`.
+pub trait DrawSurface {
+    /// Width and height in the surface's own units.
+    fn size(&self) -> (f64, f64);
+
+    fn fill_rect(&mut self, rect: Rect, color: &str, opacity: f64);
+
+    fn text(&mut self, text: &str, x: f64, y: f64, style: TextStyle);
+}
+
+/// What is drawn around the board: the score line and the win and pause overlays.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Hud {
+    pub score: u32,
+    pub best_score: u32,
+    pub win_pending: bool,
+    pub paused: bool,
+}
+
+impl From<&GameService> for Hud {
+    fn from(game: &GameService) -> Self {
+        Hud { score: game.score(), best_score: game.best_score(), win_pending: game.win_pending(), paused: false }
+    }
+}
+
+/// Draws boards and move animations; implemented for every `DrawSurface`.
+pub trait Renderer {
+    fn render(&mut self, board: &Board, hud: &Hud);
+
+    /// `board` is the position after the move; `progress` runs from 0 to 1.
+    fn render_frame(&mut self, board: &Board, hud: &Hud, outcome: &MoveOutcome, progress: f64);
+}
+
+impl<S: DrawSurface> Renderer for S {
+    fn render(&mut self, board: &Board, hud: &Hud) {
+        let layout = draw_grid(self, board);
+        for sprite in static_sprites(board) {
+            draw_sprite(self, &layout, sprite);
+        }
+        draw_scores(self, hud);
+        if hud.paused {
+            draw_pause_overlay(self, &layout);
+        } else if hud.win_pending {
+            draw_win_overlay(self, &layout);
+        }
+    }
+
+    fn render_frame(&mut self, board: &Board, hud: &Hud, outcome: &MoveOutcome, progress: f64) {
+        let layout = draw_grid(self, board);
+        for sprite in animated_sprites(board, outcome, progress) {
+            draw_sprite(self, &layout, sprite);
+        }
+        draw_scores(self, hud);
+    }
+}
+
+/// Background and empty cells.
+fn draw_grid(surface: &mut impl DrawSurface, board: &Board) -> Layout {
+    let (w, h) = surface.size();
+    let layout = Layout::for_board(board, w, h);
+    surface.fill_rect(Rect { x: 0.0, y: 0.0, w, h }, palette::BACKGROUND, 1.0);
+    for r in 0..board.rows {
+        for c in 0..board.cols {
+            surface.fill_rect(layout.cell(c as f64, r as f64, 1.0), palette::EMPTY_CELL, 1.0);
+        }
+    }
+    layout
+}
+
+fn draw_sprite(surface: &mut impl DrawSurface, layout: &Layout, sprite: Sprite) {
+    let rect = layout.cell(sprite.grid_x, sprite.grid_y, sprite.scale);
+    surface.fill_rect(rect, palette::tile_color(sprite.value), 1.0);
+    let (cx, cy) = rect.center();
+    let style = TextStyle {
+        size: layout.font_size(sprite.scale),
+        bold: false,
+        color: palette::tile_text_color(sprite.value),
+        align: TextAlign::Center,
+        middle: true,
+    };
+    surface.text(&sprite.value.to_string(), cx, cy, style);
+}
+
+fn draw_scores(surface: &mut impl DrawSurface, hud: &Hud) {
+    let (w, h) = surface.size();
+    let style = TextStyle { size: 16.0, bold: false, color: palette::TEXT, align: TextAlign::Left, middle: false };
+    surface.text(&format!("Skor: {}", hud.score), 10.0, h - 10.0, style);
+    surface.text(&format!("En iyi: {}", hud.best_score), w - 10.0, h - 10.0, TextStyle { align: TextAlign::Right, ..style });
+}
+
+fn draw_win_overlay(surface: &mut impl DrawSurface, layout: &Layout) {
+    let grid = layout.grid();
+    surface.fill_rect(grid, palette::WIN_OVERLAY, 0.5);
+    let (cx, cy) = grid.center();
+    let title = TextStyle { size: 40.0, bold: true, color: palette::TEXT_LIGHT, align: TextAlign::Center, middle: true };
+    surface.text("Kazandın!", cx, cy - 20.0, title);
+    // no key names: the browser's keys can be rebound
+    surface.text("Devam et ya da yeni oyuna başla", cx, cy + 24.0, TextStyle { size: 18.0, bold: false, ..title });
+}
+
+fn draw_pause_overlay(surface: &mut impl DrawSurface, layout: &Layout) {
+    let grid = layout.grid();
+    surface.fill_rect(grid, palette::BACKGROUND, 0.7);
+    let (cx, cy) = grid.center();
+    let title = TextStyle { size: 36.0, bold: true, color: palette::TEXT_LIGHT, align: TextAlign::Center, middle: true };
+    surface.text("Duraklatıldı", cx, cy, title);
+}
@@ -0,0 +1,94 @@
+//! SVG output through the shared renderer, so exported images look like
+//! the canvas.
+
+use crate::application::game_service::GameService;
+use crate::domain::board::{Board, MoveOutcome};
+use crate::infra::layout::Rect;
+use crate::infra::surface::{DrawSurface, Hud, Renderer, TextAlign, TextStyle};
+
+/// Same size as the canvas in `index.html`.
+pub const DEFAULT_SVG_WIDTH: f64 = 420.0;
+pub const DEFAULT_SVG_HEIGHT: f64 = 520.0;
+
+/// Collects drawing calls as SVG elements; `finish` wraps them in a document.
+pub struct SvgSurface {
+    width: f64,
+    height: f64,
+    body: String,
+}
+
+/// Up to two decimals, without trailing zeros, to keep the markup small.
+fn num(v: f64) -> String {
+    let s = format!("{:.2}", v);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl SvgSurface {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self { width, height, body: String::new() }
+    }
+
+    pub fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{}</svg>\n",
+            self.body,
+            w = num(self.width),
+            h = num(self.height),
+        )
+    }
+}
+
+impl DrawSurface for SvgSurface {
+    fn size(&self) -> (f64, f64) { (self.width, self.height) }
+
+    fn fill_rect(&mut self, rect: Rect, color: &str, opacity: f64) {
+        let opacity = if opacity < 1.0 { format!(" fill-opacity=\"{}\"", num(opacity)) } else { String::new() };
+        self.body.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"{}/>\n",
+            num(rect.x), num(rect.y), num(rect.w), num(rect.h), color, opacity,
+        ));
+    }
+
+    fn text(&mut self, text: &str, x: f64, y: f64, style: TextStyle) {
+        let anchor = match style.align {
+            TextAlign::Left => "start",
+            TextAlign::Center => "middle",
+            TextAlign::Right => "end",
+        };
+        let baseline = if style.middle { " dominant-baseline=\"central\"" } else { "" };
+        let weight = if style.bold { " font-weight=\"bold\"" } else { "" };
+        self.body.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-family=\"Clear Sans, Arial\" font-size=\"{}\"{} fill=\"{}\" text-anchor=\"{}\"{}>{}</text>\n",
+            num(x), num(y), num(style.size), weight, style.color, anchor, baseline, escape(text),
+        ));
+    }
+}
+
+pub fn board_svg(board: &Board, hud: &Hud, width: f64, height: f64) -> String {
+    let mut surface = SvgSurface::new(width, height);
+    surface.render(board, hud);
+    surface.finish()
+}
+
+/// One frame of `outcome` animating into `board`, `progress` from 0 to 1.
+pub fn frame_svg(board: &Board, hud: &Hud, outcome: &MoveOutcome, progress: f64, width: f64, height: f64) -> String {
+    let mut surface = SvgSurface::new(width, height);
+    surface.render_frame(board, hud, outcome, progress);
+    surface.finish()
+}
+
+impl GameService {
+    /// The current board as the canvas would draw it, as an SVG document.
+    pub fn to_svg(&self) -> String {
+        board_svg(self.board(), &Hud::from(self), DEFAULT_SVG_WIDTH, DEFAULT_SVG_HEIGHT)
+    }
+
+    /// A frame of `outcome` (the last move played) at `progress`.
+    pub fn frame_svg(&self, outcome: &MoveOutcome, progress: f64) -> String {
+        frame_svg(self.board(), &Hud::from(self), outcome, progress, DEFAULT_SVG_WIDTH, DEFAULT_SVG_HEIGHT)
+    }
+}
@@ -1,144 +1,991 @@
+use serde::Serialize;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::closure::Closure;
-use web_sys::{window, KeyboardEvent};
+use web_sys::{window, Event, EventTarget, HtmlCanvasElement, KeyboardEvent, PointerEvent};
 
+use crate::ai::expectimax::SearchBudget;
+use crate::ai::ntuple::NTupleNetwork;
 use crate::application::game_service::GameService;
+use crate::application::replay::ReplayPlayer;
+use crate::application::snapshot::{GameSnapshot, SnapshotSource};
+use crate::infra::export::{export_recording, AnimationFormat, ExportOptions};
+use crate::infra::gamepad::{GamepadInput, GamepadMapping, PadState};
+use crate::infra::gesture::{SwipeConfig, SwipeDetector};
+use crate::infra::input::{InputAction, InputMap};
+use crate::infra::layout::MOVE_ANIMATION_MS;
+use crate::infra::move_queue::MoveQueue;
+use crate::infra::storage::GameStorage;
+use crate::infra::surface::{Hud, Renderer};
+use crate::infra::svg::{board_svg, frame_svg, DEFAULT_SVG_HEIGHT, DEFAULT_SVG_WIDTH};
+use crate::domain::board::MoveOutcome;
+use crate::domain::config::GameConfig;
 use crate::domain::direction::Direction;
 
 #[wasm_bindgen]
 pub struct WasmGameService {
     inner: GameService,
+    autoplay: Option<Autoplay>,
+    swipe: SwipeDetector,
+    gamepad: GamepadInput,
+    paused: bool,
+    animation_ms: f64,
+    queue: MoveQueue,
+    /// Cut the running animation short as soon as a move is queued.
+    fast_forward: bool,
+}
+
+/// Moves per second the solver plays when `autoplay` is given no speed.
+const DEFAULT_AUTOPLAY_SPEED: f64 = 8.0;
+
+/// Autoplay searches inside the animation frame callback, so it gets a
+/// fraction of a 60 Hz frame instead of the full hint budget.
+const AUTOPLAY_BUDGET: SearchBudget = SearchBudget { max_depth: 3, time_ms: Some(6.0) };
+
+#[derive(Clone, Copy)]
+struct Autoplay {
+    interval_ms: f64,
+    next_ms: f64,
 }
 
 #[wasm_bindgen]
 impl WasmGameService {
     #[wasm_bindgen(constructor)]
-    pub fn new(size: usize) -> WasmGameService {
-        WasmGameService { inner: GameService::new(size) }
+    pub fn new(size: usize) -> Result<WasmGameService, JsValue> {
+        WasmGameService::checked(GameConfig::new(size))
+    }
+
+    pub fn with_dims(rows: usize, cols: usize) -> Result<WasmGameService, JsValue> {
+        WasmGameService::checked(GameConfig::rect(rows, cols))
+    }
+
+    pub fn with_seed(size: usize, seed: u64) -> Result<WasmGameService, JsValue> {
+        WasmGameService::checked(GameConfig { seed: Some(seed), ..GameConfig::new(size) })
+    }
+
+    /// `win_target` is the tile that wins (e.g. 512 or 8192); leave `seed` undefined for a random game.
+    pub fn with_config(size: usize, win_target: u32, seed: Option<u64>) -> Result<WasmGameService, JsValue> {
+        WasmGameService::checked(GameConfig { win_target, seed, ..GameConfig::new(size) })
+    }
+
+    /// Full configuration as a plain object, e.g.
+    /// `{ rows: 3, cols: 5, win_target: 2048, spawn: { values: [[2, 9], [4, 1]], per_move: 1, initial: 2, policy: "CornerBiased" } }`.
+    /// Missing fields keep their defaults. Boards have at most 16 rows and columns.
+    pub fn from_config(config: JsValue) -> Result<WasmGameService, JsValue> {
+        WasmGameService::checked(serde_wasm_bindgen::from_value(config)?)
     }
 
     pub fn reset(&mut self) { self.inner.reset(); }
     pub fn score(&self) -> u32 { self.inner.score() }
     pub fn is_over(&self) -> bool { self.inner.is_over() }
     pub fn is_won(&self) -> bool { self.inner.is_won() }
+    pub fn win_target(&self) -> u32 { self.inner.config().win_target }
+    /// True once the target is reached until `keep_playing` is called; moves are ignored meanwhile.
+    pub fn win_pending(&self) -> bool { self.inner.win_pending() }
+    pub fn keep_playing(&mut self) { self.inner.keep_playing(); }
 
     pub fn slide_left(&mut self) -> bool { self.inner.slide(Direction::Left) }
     pub fn slide_right(&mut self) -> bool { self.inner.slide(Direction::Right) }
     pub fn slide_up(&mut self) -> bool { self.inner.slide(Direction::Up) }
     pub fn slide_down(&mut self) -> bool { self.inner.slide(Direction::Down) }
+
+    pub fn undo(&mut self) -> bool { self.inner.undo() }
+    pub fn redo(&mut self) -> bool { self.inner.redo() }
+    pub fn can_undo(&self) -> bool { self.inner.can_undo() }
+    pub fn can_redo(&self) -> bool { self.inner.can_redo() }
+    /// Leave `depth` undefined for unlimited history, 0 disables undo.
+    pub fn set_undo_depth(&mut self, depth: Option<u32>) { self.inner.set_undo_depth(depth.map(|d| d as usize)); }
+
+    /// Lets the solver play by itself; `speed` is in moves per second. Only the
+    /// game driven by `start` acts on it, once per animation frame.
+    pub fn autoplay(&mut self, enabled: bool, speed: Option<f64>) {
+        let speed = speed.filter(|s| *s > 0.0).unwrap_or(DEFAULT_AUTOPLAY_SPEED);
+        self.autoplay = enabled.then_some(Autoplay { interval_ms: 1000.0 / speed, next_ms: 0.0 });
+    }
+
+    pub fn is_autoplaying(&self) -> bool { self.autoplay.is_some() }
+
+    /// A paused game ignores every input but unpausing, and stops autoplay.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        if paused {
+            self.autoplay = None;
+            self.queue.clear();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool { self.paused }
+
+    /// How long a move animates, in milliseconds; 0 moves tiles instantly.
+    pub fn set_animation_ms(&mut self, ms: f64) -> Result<(), JsValue> {
+        // Infinity would leave the animation, and every queued move behind it, unfinished forever
+        if !ms.is_finite() || ms < 0.0 { return Err(JsValue::from_str("Animasyon suresi sonlu ve negatif olmayan bir sayi olmali")); }
+        self.animation_ms = ms;
+        Ok(())
+    }
+
+    pub fn animation_ms(&self) -> f64 { self.animation_ms }
+
+    /// Buffers up to `capacity` moves made while a move animates (0 drops
+    /// them); with `fast_forward` the running animation jumps to its end as
+    /// soon as one is waiting.
+    pub fn set_move_queue(&mut self, capacity: usize, fast_forward: Option<bool>) {
+        self.queue.set_capacity(capacity);
+        self.fast_forward = fast_forward.unwrap_or(false);
+    }
+
+    pub fn queued_moves(&self) -> usize { self.queue.len() }
+
+    /// Tunes swipes on the canvas: `min_distance` in CSS pixels, `min_velocity`
+    /// in pixels per millisecond, `angle_tolerance` in degrees off the axis
+    /// (at most 45). Undefined values keep their current setting.
+    pub fn set_swipe(&mut self, min_distance: Option<f64>, min_velocity: Option<f64>, angle_tolerance: Option<f64>) -> Result<(), JsValue> {
+        let current = self.swipe.config();
+        let config = SwipeConfig {
+            min_distance: min_distance.unwrap_or(current.min_distance),
+            min_velocity: min_velocity.unwrap_or(current.min_velocity),
+            angle_tolerance: angle_tolerance.unwrap_or(current.angle_tolerance),
+        };
+        config.validate().map_err(|e| JsValue::from_str(&e))?;
+        self.swipe.set_config(config);
+        Ok(())
+    }
+
+    /// Controller mapping as a plain object, e.g.
+    /// `{ buttons: [[12, "up"], [13, "down"], [14, "left"], [15, "right"], [1, "undo"], [3, "restart"]], stick: [0, 1], dead_zone: 0.5, repeat_delay_ms: 300, repeat_interval_ms: 150 }`.
+    /// Actions are `up`, `down`, `left`, `right`, `restart`, `undo`, `redo` and
+    /// `continue`; `stick: null` turns the stick off. Missing fields keep their defaults.
+    pub fn set_gamepad_mapping(&mut self, mapping: JsValue) -> Result<(), JsValue> {
+        let mapping: GamepadMapping = serde_wasm_bindgen::from_value(mapping)?;
+        mapping.validate().map_err(|e| JsValue::from_str(&e))?;
+        self.gamepad.set_mapping(mapping);
+        Ok(())
+    }
+
+    pub fn gamepad_mapping(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(self.gamepad.mapping()).map_err(JsValue::from)
+    }
+
+    /// Lets a trained n-tuple network (the trainer's weight file) drive `hint`
+    /// and autoplay. It only plays 4x4 games: loading it into another size
+    /// fails, and a new game of another size goes back to expectimax.
+    pub fn load_ntuple(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
+        let network = NTupleNetwork::from_bytes(bytes).map_err(|e| JsValue::from_str(&e))?;
+        self.inner.set_strategy(Some(Box::new(network))).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Goes back to the built-in expectimax search.
+    pub fn use_expectimax(&mut self) { let _ = self.inner.set_strategy(None); }
+
+    pub fn strategy_name(&self) -> String { self.inner.strategy_name().to_string() }
+
+    pub fn moves(&self) -> u32 { self.inner.moves() }
+    pub fn best_score(&self) -> u32 { self.inner.best_score() }
+
+    /// `{ direction: "up" | "down" | "left" | "right", score }`, or `null` when no move is possible.
+    pub fn hint(&mut self) -> Result<JsValue, JsValue> {
+        match self.inner.best_move() {
+            Some((direction, score)) => {
+                let hint = Hint { direction: direction.as_str(), score };
+                serde_wasm_bindgen::to_value(&hint).map_err(JsValue::from)
+            }
+            None => Ok(JsValue::NULL),
+        }
+    }
+
+    pub fn snapshot(&self) -> Result<JsValue, JsValue> {
+        let serializer = serde_wasm_bindgen::Serializer::new().serialize_large_number_types_as_bigints(true);
+        self.inner.snapshot().serialize(&serializer).map_err(JsValue::from)
+    }
+
+    pub fn from_snapshot(value: JsValue) -> Result<WasmGameService, JsValue> {
+        let snapshot = GameSnapshot::load(JsSource(value)).map_err(|e| JsValue::from_str(&e))?;
+        Ok(WasmGameService::from(GameService::from_snapshot(snapshot)))
+    }
+
+    /// The board as an SVG document; size defaults to the page's canvas.
+    pub fn to_svg(&self, width: Option<f64>, height: Option<f64>) -> String {
+        let width = width.unwrap_or(DEFAULT_SVG_WIDTH);
+        let height = height.unwrap_or(DEFAULT_SVG_HEIGHT);
+        board_svg(self.inner.board(), &Hud::from(&self.inner), width, height)
+    }
+
+    /// Records every following move until `stop_recording`, for `export_recording`.
+    pub fn start_recording(&mut self) { self.inner.start_recording(); }
+    pub fn stop_recording(&mut self) { self.inner.stop_recording(); }
+    pub fn is_recording(&self) -> bool { self.inner.recording().is_some() }
+
+    /// The moves recorded so far as an animated image; `format` is `"gif"` or `"apng"`.
+    pub fn export_recording(&self, format: &str, fps: Option<u32>, width: Option<u32>, height: Option<u32>) -> Result<Vec<u8>, JsValue> {
+        let recording = self.inner.recording().ok_or_else(|| JsValue::from_str("Kayit baslatilmadi"))?;
+        let format: AnimationFormat = format.parse().map_err(|e: String| JsValue::from_str(&e))?;
+        let defaults = ExportOptions::default();
+        let options = ExportOptions {
+            fps: fps.unwrap_or(defaults.fps),
+            width: width.unwrap_or(defaults.width),
+            height: height.unwrap_or(defaults.height),
+            ..defaults
+        };
+        export_recording(recording, format, &options).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// The game since it started as a replay file (JSON), for `WasmReplayPlayer`.
+    pub fn replay_json(&self) -> Result<String, JsValue> {
+        let replay = self.inner.replay().ok_or_else(|| JsValue::from_str("Bu oyunun tekrar kaydi yok"))?;
+        replay.to_json().map_err(|e| JsValue::from_str(&e))
+    }
+
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        self.inner.snapshot().to_json().map_err(|e| JsValue::from_str(&e))
+    }
+
+    pub fn from_json(json: &str) -> Result<WasmGameService, JsValue> {
+        let snapshot = GameSnapshot::from_json(json).map_err(|e| JsValue::from_str(&e))?;
+        Ok(WasmGameService::from(GameService::from_snapshot(snapshot)))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        self.inner.snapshot().to_bytes().map_err(|e| JsValue::from_str(&e))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmGameService, JsValue> {
+        let snapshot = GameSnapshot::from_bytes(bytes).map_err(|e| JsValue::from_str(&e))?;
+        Ok(WasmGameService::from(GameService::from_snapshot(snapshot)))
+    }
+}
+
+impl From<GameService> for WasmGameService {
+    fn from(inner: GameService) -> Self {
+        Self {
+            inner,
+            autoplay: None,
+            swipe: SwipeDetector::default(),
+            gamepad: GamepadInput::default(),
+            paused: false,
+            animation_ms: MOVE_ANIMATION_MS,
+            queue: MoveQueue::default(),
+            fast_forward: false,
+        }
+    }
+}
+
+impl WasmGameService {
+    /// A new game under `config`, refused with the reason when it is invalid.
+    fn checked(config: GameConfig) -> Result<WasmGameService, JsValue> {
+        config.validate().map_err(|e| JsValue::from_str(&e))?;
+        Ok(WasmGameService::from(GameService::with_config(config)))
+    }
+
+    /// The solver's next move if autoplay is on and due at `now_ms`; turns
+    /// autoplay off once no move is left.
+    fn next_autoplay_move(&mut self, now_ms: f64) -> Option<Direction> {
+        let autoplay = self.autoplay.as_mut().filter(|a| now_ms >= a.next_ms)?;
+        autoplay.next_ms = now_ms + autoplay.interval_ms;
+        let direction = self.inner.best_move_within(AUTOPLAY_BUDGET).map(|(d, _)| d);
+        if direction.is_none() { self.autoplay = None; }
+        direction
+    }
+
+    fn hud(&self) -> Hud {
+        Hud { paused: self.paused, ..Hud::from(&self.inner) }
+    }
+}
+
+/// Steps through a replay file after checking that it plays out to its
+/// stored result.
+#[wasm_bindgen]
+pub struct WasmReplayPlayer {
+    inner: ReplayPlayer,
+}
+
+#[wasm_bindgen]
+impl WasmReplayPlayer {
+    /// Fails with the reason when the file is damaged or does not replay.
+    pub fn from_json(json: &str) -> Result<WasmReplayPlayer, JsValue> {
+        let inner = ReplayPlayer::from_json(json).map_err(|e| JsValue::from_str(&e))?;
+        Ok(WasmReplayPlayer { inner })
+    }
+
+    pub fn step_forward(&mut self) -> bool { self.inner.step_forward().is_some() }
+    pub fn step_back(&mut self) -> bool { self.inner.step_back().is_some() }
+    pub fn seek(&mut self, position: usize) { self.inner.seek(position); }
+    /// Actions played so far; 0 is the starting position.
+    pub fn position(&self) -> usize { self.inner.position() }
+    pub fn len(&self) -> usize { self.inner.len() }
+    pub fn is_empty(&self) -> bool { self.inner.is_empty() }
+    pub fn score(&self) -> u32 { self.inner.frame().board.score }
+    pub fn moves(&self) -> u32 { self.inner.frame().moves }
+    /// The last action played (`"up"`, ..., `"undo"`, `"redo"`, `"continue"`), or `null` at the start.
+    pub fn action(&self) -> Option<String> { self.inner.frame().step.map(|s| s.action.as_str().to_string()) }
+    /// When the last action was played, in milliseconds since the game started.
+    pub fn time_ms(&self) -> f64 { self.inner.frame().step.map_or(0.0, |s| s.t_ms as f64) }
+
+    /// Draws the current position on the canvas; with `progress` below 1 the
+    /// last move is drawn part way through its animation.
+    pub fn draw(&self, canvas_id: &str, progress: Option<f64>) -> Result<(), JsValue> {
+        let window = window().ok_or("no window")?;
+        let document = window.document().ok_or("no document")?;
+        let mut renderer = crate::infra::render2d::Canvas2DRenderer::new(&window, &document, canvas_id)
+            .map_err(|e| JsValue::from_str(&e))?;
+        let frame = self.inner.frame();
+        match progress.filter(|p| *p < 1.0 && frame.outcome.moved) {
+            Some(progress) => renderer.render_frame(&frame.board, &self.hud(), &frame.outcome, progress),
+            None => renderer.render(&frame.board, &self.hud()),
+        }
+        Ok(())
+    }
+
+    pub fn to_svg(&self, width: Option<f64>, height: Option<f64>, progress: Option<f64>) -> String {
+        let width = width.unwrap_or(DEFAULT_SVG_WIDTH);
+        let height = height.unwrap_or(DEFAULT_SVG_HEIGHT);
+        let frame = self.inner.frame();
+        match progress.filter(|p| *p < 1.0 && frame.outcome.moved) {
+            Some(progress) => frame_svg(&frame.board, &self.hud(), &frame.outcome, progress, width, height),
+            None => board_svg(&frame.board, &self.hud(), width, height),
+        }
+    }
+}
+
+impl WasmReplayPlayer {
+    fn hud(&self) -> Hud {
+        let frame = self.inner.frame();
+        Hud { score: frame.board.score, best_score: frame.best_score, win_pending: frame.win_pending, paused: false }
+    }
+}
+
+#[derive(Serialize)]
+struct Hint {
+    direction: &'static str,
+    score: f64,
+}
+
+/// Snapshot passed in from JS as a plain object (as produced by `snapshot()`).
+struct JsSource(JsValue);
+
+impl SnapshotSource for JsSource {
+    fn version(&self) -> Result<u32, String> {
+        js_sys::Reflect::get(&self.0, &JsValue::from_str("version"))
+            .ok()
+            .and_then(|v| v.as_f64())
+            .map(|v| v as u32)
+            .ok_or_else(|| "Kayit surumu bulunamadi".to_string())
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(self) -> Result<T, String> {
+        serde_wasm_bindgen::from_value(self.0).map_err(|e| format!("Kayit okunamadi: {}", e))
+    }
 }
 
 use std::cell::RefCell;
 use std::rc::Rc;
-thread_local! {
-    static GLOBAL_GAME: RefCell<Option<Rc<RefCell<WasmGameService>>>> = RefCell::new(None);
-    static GLOBAL_CANVAS_ID: RefCell<Option<String>> = RefCell::new(None);
-    static GLOBAL_ANIM: RefCell<Option<AnimState>> = RefCell::new(None);
-}
+
+use crate::infra::render2d::Canvas2DRenderer;
 
 #[derive(Clone)]
 struct AnimState {
-    moves: Vec<crate::domain::board::MoveEvent>,
+    outcome: MoveOutcome,
     start_ms: f64,
     duration_ms: f64,
-    direction: Direction,
 }
 
-#[wasm_bindgen]
-pub fn start(canvas_id: String) -> Result<(), JsValue> {
-    console_error_panic_hook::set_once();
-    let window = window().ok_or("no window")?;
-    let document = window.document().ok_or("no document")?;
+/// Everything one board on the page plays with.
+struct Instance {
+    game: WasmGameService,
+    canvas: HtmlCanvasElement,
+    anim: Option<AnimState>,
+    storage: GameStorage,
+    input: InputMap,
+    /// Index of the controller driving this board; `None` takes the first connected one.
+    gamepad_index: Option<u32>,
+    keyboard: KeyboardScope,
+}
+
+/// Which key presses a board reads. Mirrored on the canvas as the
+/// `data-game2048-keyboard` attribute, so boards can see each other.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KeyboardScope {
+    /// Keys anywhere on the page, except on another board's canvas.
+    Page,
+    /// Only keys pressed while its canvas has focus.
+    Focus,
+    Off,
+}
 
-    let game_rc = Rc::new(RefCell::new(WasmGameService::new(4)));
-    GLOBAL_GAME.with(|g| g.replace(Some(Rc::clone(&game_rc))));
-    GLOBAL_CANVAS_ID.with(|c| c.replace(Some(canvas_id.clone())));
+const KEYBOARD_ATTRIBUTE: &str = "data-game2048-keyboard";
 
-    {
-        let renderer = crate::infra::render2d::Canvas2DRenderer::new(&window, &document, &canvas_id)
-            .map_err(|e| JsValue::from_str(&e))?;
-        renderer.draw(&game_rc.borrow().inner).map_err(|e| JsValue::from_str(&e))?;
-    }
-
-    // RAF loop
-    {
-        let f: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
-        let g = f.clone();
-        let canvas_id_for_draw = canvas_id.clone();
-        *g.borrow_mut() = Some(Closure::<dyn FnMut(f64)>::wrap(Box::new(move |now: f64| {
-            let maybe_anim = GLOBAL_ANIM.with(|a| a.borrow().clone());
-            if let Some(anim) = maybe_anim {
-                let progress = ((now - anim.start_ms) / anim.duration_ms).clamp(0.0, 1.0);
-                if let (Some(w), Some(d)) = (web_sys::window(), web_sys::window().and_then(|w| w.document())) {
-                    GLOBAL_GAME.with(|g| {
-                        if let Some(gref) = g.borrow().as_ref() {
-                            if let Ok(renderer) = crate::infra::render2d::Canvas2DRenderer::new(&w, &d, &canvas_id_for_draw) {
-                                let _ = renderer.draw_animated(&gref.borrow().inner, &anim.moves, progress);
-                            }
-                        }
-                    });
-                }
-                if progress >= 1.0 {
-                    // finalize: apply real slide and draw static board
-                    GLOBAL_GAME.with(|g| {
-                        if let Some(gref) = g.borrow().as_ref() {
-                            let mut real = gref.borrow_mut();
-                            let _ = real.inner.slide(anim.direction);
-                            if let (Some(w), Some(d)) = (web_sys::window(), web_sys::window().and_then(|w| w.document())) {
-                                if let Ok(renderer) = crate::infra::render2d::Canvas2DRenderer::new(&w, &d, &canvas_id_for_draw) {
-                                    let _ = renderer.draw(&real.inner);
-                                }
-                            }
-                        }
-                    });
-                    GLOBAL_ANIM.with(|a| a.replace(None));
+impl KeyboardScope {
+    const ALL: [KeyboardScope; 3] = [KeyboardScope::Page, KeyboardScope::Focus, KeyboardScope::Off];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            KeyboardScope::Page => "page",
+            KeyboardScope::Focus => "focus",
+            KeyboardScope::Off => "off",
+        }
+    }
+}
+
+/// An event listener kept so `destroy` can remove it.
+struct Listener {
+    target: EventTarget,
+    kind: &'static str,
+    callback: Closure<dyn FnMut(Event)>,
+}
+
+/// What ties a board to the page: its animation frame loop and listeners.
+#[derive(Default)]
+struct Hooks {
+    frame: Option<Closure<dyn FnMut(f64)>>,
+    frame_id: Option<i32>,
+    listeners: Vec<Listener>,
+    destroyed: bool,
+}
+
+/// Shared by the handle and every callback of one board. The callbacks hold
+/// clones of it, so a board keeps running when JS drops its handle, until
+/// `destroy` drops the callbacks.
+#[derive(Clone)]
+struct Runtime {
+    state: Rc<RefCell<Instance>>,
+    hooks: Rc<RefCell<Hooks>>,
+}
+
+impl Runtime {
+    fn redraw(&self) {
+        let state = self.state.borrow();
+        if let Ok(mut renderer) = Canvas2DRenderer::from_canvas(&state.canvas) {
+            renderer.render(state.game.inner.board(), &state.game.hud());
+        }
+    }
+
+    fn persist(&self) {
+        let state = self.state.borrow();
+        state.storage.save_game(&state.game.inner.snapshot());
+        state.storage.save_best_score(state.game.inner.best_score());
+    }
+
+    fn persist_input_map(&self) {
+        let state = self.state.borrow();
+        state.storage.save_input_map(&state.input);
+    }
+
+    /// Fires `name` on the canvas with `detail`. Listeners may call back into
+    /// the handle, so nothing stays borrowed meanwhile.
+    fn dispatch(&self, name: &str, detail: &JsValue) {
+        let canvas = self.state.borrow().canvas.clone();
+        let init = web_sys::CustomEventInit::new();
+        init.set_detail(detail);
+        if let Ok(event) = web_sys::CustomEvent::new_with_event_init_dict(name, &init) {
+            let _ = canvas.dispatch_event(&event);
+        }
+    }
+
+    /// Fires `game2048:win` on the canvas (detail: score) the first time the target is reached.
+    fn notify_win(&self, score: u32) {
+        self.dispatch("game2048:win", &JsValue::from(score));
+    }
+
+    /// Fires `game2048:hint` on the canvas with the solver's suggestion as
+    /// detail (`{ direction, score }`, or `null` when no move is left).
+    fn show_hint(&self) {
+        let hint = self.state.borrow_mut().game.hint();
+        if let Ok(detail) = hint { self.dispatch("game2048:hint", &detail); }
+    }
+
+    /// Applies `direction` to the game right away and animates the recorded
+    /// outcome, or just redraws in instant mode; every input device, the
+    /// move queue and autoplay go through here.
+    fn begin_move(&self, direction: Direction) {
+        let outcome = self.state.borrow_mut().game.inner.apply_move(direction);
+        if !outcome.moved { return; }
+        self.persist();
+        if outcome.won {
+            let score = self.state.borrow().game.score();
+            self.notify_win(score);
+        }
+        let duration_ms = self.state.borrow().game.animation_ms;
+        if duration_ms <= 0.0 {
+            self.redraw();
+            return;
+        }
+        let start_ms = window().unwrap().performance().unwrap().now();
+        self.state.borrow_mut().anim = Some(AnimState { outcome, start_ms, duration_ms });
+    }
+
+    /// Stops autoplay on any player input; true if it was running, in which
+    /// case the input only takes control back and does nothing else.
+    fn take_control(&self) -> bool {
+        let mut state = self.state.borrow_mut();
+        let playing = state.game.is_autoplaying();
+        if playing { state.game.autoplay(false, None); }
+        playing
+    }
+
+    fn step_history(&self, forward: bool) {
+        let changed = {
+            let mut state = self.state.borrow_mut();
+            if forward { state.game.redo() } else { state.game.undo() }
+        };
+        if changed {
+            self.persist();
+            self.redraw();
+        }
+    }
+
+    /// Keeps playing after a win; does nothing otherwise.
+    fn continue_game(&self) {
+        let continued = {
+            let mut state = self.state.borrow_mut();
+            let pending = state.game.win_pending();
+            if pending { state.game.keep_playing(); }
+            pending
+        };
+        if continued {
+            self.persist();
+            self.redraw();
+        }
+    }
+
+    fn restart(&self) {
+        self.state.borrow_mut().game.reset();
+        self.persist();
+        self.redraw();
+    }
+
+    /// Polls this board's controller once per animation frame and plays
+    /// what it asks for, with the same rules as keys.
+    fn poll_gamepad(&self, now_ms: f64) {
+        let index = self.state.borrow().gamepad_index;
+        let Some(pad) = read_gamepad(index) else { return };
+        let actions = self.state.borrow_mut().game.gamepad.update(&pad, now_ms);
+        for action in actions {
+            self.perform(action);
+        }
+    }
+
+    fn toggle_pause(&self) {
+        {
+            let mut state = self.state.borrow_mut();
+            let paused = state.game.is_paused();
+            state.game.set_paused(!paused);
+        }
+        self.redraw();
+    }
+
+    /// Plays an action from any input device. Input first takes control back
+    /// from autoplay; a paused game only listens for unpausing. While a move
+    /// animates, directions wait in the move queue and other actions are dropped.
+    fn perform(&self, action: InputAction) {
+        if self.hooks.borrow().destroyed || self.take_control() { return; }
+        if action == InputAction::Pause {
+            self.toggle_pause();
+            return;
+        }
+        if self.state.borrow().game.is_paused() { return; }
+        if action == InputAction::Autoplay {
+            self.state.borrow_mut().game.autoplay(true, None);
+            return;
+        }
+        let animating = self.state.borrow().anim.is_some();
+        if animating {
+            if let InputAction::Move(direction) = action { self.state.borrow_mut().game.queue.push(direction); }
+            return;
+        }
+        match action {
+            InputAction::Move(direction) => self.begin_move(direction),
+            InputAction::Restart => self.restart(),
+            InputAction::Undo => self.step_history(false),
+            InputAction::Redo => self.step_history(true),
+            InputAction::Continue => self.continue_game(),
+            InputAction::Hint => self.show_hint(),
+            InputAction::Pause | InputAction::Autoplay => {}
+        }
+    }
+
+    /// One animation frame: advances the running animation, polls the
+    /// controller and starts the next queued or autoplayed move.
+    fn frame(&self, now: f64) {
+        let anim = self.state.borrow().anim.clone();
+        if let Some(anim) = anim {
+            let progress = {
+                let state = self.state.borrow();
+                let skip = state.game.fast_forward && !state.game.queue.is_empty();
+                let progress = if skip { 1.0 } else { ((now - anim.start_ms) / anim.duration_ms).clamp(0.0, 1.0) };
+                if let Ok(mut renderer) = Canvas2DRenderer::from_canvas(&state.canvas) {
+                    renderer.render_frame(state.game.inner.board(), &state.game.hud(), &anim.outcome, progress);
                 }
+                progress
+            };
+            if progress >= 1.0 {
+                // the move was applied when it started; just settle on the static board
+                self.state.borrow_mut().anim = None;
+                self.redraw();
             }
-            if let Some(w) = web_sys::window() {
-                let _ = w.request_animation_frame(f.borrow().as_ref().unwrap().as_ref().unchecked_ref());
-            }
-        })));
-        if let Some(w) = web_sys::window() {
-            let _ = w.request_animation_frame(g.borrow().as_ref().unwrap().as_ref().unchecked_ref());
         }
+        self.poll_gamepad(now);
+        let next = {
+            let mut state = self.state.borrow_mut();
+            if state.anim.is_some() { return; }
+            let game = &mut state.game;
+            game.queue.pop().or_else(|| game.next_autoplay_move(now))
+        };
+        if let Some(direction) = next { self.begin_move(direction); }
     }
 
-    {
-        let closure = Closure::wrap(Box::new(move |event: KeyboardEvent| {
-            // ignore input if animating
-            let anim_busy = GLOBAL_ANIM.with(|a| a.borrow().is_some());
-            if anim_busy { return; }
+    /// Runs `frame` on every animation frame until `destroy`.
+    fn run_frames(&self) {
+        let runtime = self.clone();
+        let frame = Closure::<dyn FnMut(f64)>::new(move |now: f64| {
+            runtime.frame(now);
+            runtime.request_frame();
+        });
+        self.hooks.borrow_mut().frame = Some(frame);
+        self.request_frame();
+    }
 
-            let dir = match event.key().as_str() {
-                "ArrowLeft" | "a" | "A" => Some(Direction::Left),
-                "ArrowRight" | "d" | "D" => Some(Direction::Right),
-                "ArrowUp" | "w" | "W" => Some(Direction::Up),
-                "ArrowDown" | "s" | "S" => Some(Direction::Down),
-                _ => None
-            };
-            if let Some(direction) = dir {
-                // prepare animation events from current board WITHOUT applying state yet
-                let (_did_move_unused, moves) = GLOBAL_GAME.with(|g| {
-                    if let Some(gref) = g.borrow().as_ref() {
-                        let gs_ref = gref.borrow();
-                        let mut board_clone = gs_ref.inner.board().clone();
-                        board_clone.slide_with_animations(direction)
-                    } else { (false, vec![]) }
-                });
-                let effective_move = moves.iter().any(|e| e.merged_into_value.is_some() || e.from_row != e.to_row || e.from_col != e.to_col);
-                if effective_move {
-                    let start = web_sys::window().unwrap().performance().unwrap().now();
-                    GLOBAL_ANIM.with(|a| a.replace(Some(AnimState { moves, start_ms: start, duration_ms: 140.0, direction })));
-                }
+    fn request_frame(&self) {
+        let mut hooks = self.hooks.borrow_mut();
+        let id = hooks.frame.as_ref().and_then(|f| window()?.request_animation_frame(f.as_ref().unchecked_ref()).ok());
+        hooks.frame_id = id;
+    }
+
+    /// Adds `callback` for `kind` events on `target` until `destroy`.
+    fn listen(&self, target: &EventTarget, kind: &'static str, callback: impl FnMut(Event) + 'static) -> Result<(), JsValue> {
+        let callback = Closure::<dyn FnMut(Event)>::new(callback);
+        target.add_event_listener_with_callback(kind, callback.as_ref().unchecked_ref())?;
+        self.hooks.borrow_mut().listeners.push(Listener { target: target.clone(), kind, callback });
+        Ok(())
+    }
+
+    /// Whether a key pressed on `target` is meant for this board.
+    fn owns_key(&self, target: Option<EventTarget>) -> bool {
+        let state = self.state.borrow();
+        let element = target.and_then(|t| t.dyn_into::<web_sys::Element>().ok());
+        let on_canvas = element.as_ref().is_some_and(|e| js_sys::Object::is(e, &state.canvas));
+        let on_other_board = !on_canvas && element.is_some_and(|e| e.has_attribute(KEYBOARD_ATTRIBUTE));
+        match state.keyboard {
+            KeyboardScope::Page => !on_other_board,
+            KeyboardScope::Focus => on_canvas,
+            KeyboardScope::Off => false,
+        }
+    }
+
+    fn set_keyboard(&self, scope: KeyboardScope) {
+        let mut state = self.state.borrow_mut();
+        state.keyboard = scope;
+        let _ = state.canvas.set_attribute(KEYBOARD_ATTRIBUTE, scope.as_str());
+    }
+
+    /// Keys on the page, read through this board's own bindings when its
+    /// keyboard scope lets them through. Keys it does not bind are left alone.
+    fn listen_for_keys(&self, document: &web_sys::Document) -> Result<(), JsValue> {
+        let runtime = self.clone();
+        self.listen(document, "keydown", move |event| {
+            let Ok(event) = event.dyn_into::<KeyboardEvent>() else { return };
+            // typing into a form field on the page is not playing
+            if is_editable(event.target()) || !runtime.owns_key(event.target()) { return; }
+            let action = runtime.state.borrow().input.action(&event.code());
+            let Some(action) = action else { return };
+            event.prevent_default();
+            if event.repeat() && !action.repeats() { return; }
+            runtime.perform(action);
+        })
+    }
+
+    /// Swipes on the canvas, from touch, pen or mouse alike.
+    fn listen_for_swipes(&self, canvas: &HtmlCanvasElement) -> Result<(), JsValue> {
+        // keep the browser from scrolling or zooming instead
+        canvas.style().set_property("touch-action", "none")?;
+
+        let runtime = self.clone();
+        let target = canvas.clone();
+        self.listen(canvas, "pointerdown", move |event| {
+            let Ok(event) = event.dyn_into::<PointerEvent>() else { return };
+            event.prevent_default();
+            // preventing the default also keeps the canvas from taking focus
+            let _ = target.focus();
+            let _ = target.set_pointer_capture(event.pointer_id());
+            let (x, y) = (event.client_x() as f64, event.client_y() as f64);
+            runtime.state.borrow_mut().game.swipe.pointer_down(event.pointer_id(), x, y, event.time_stamp());
+        })?;
+
+        let runtime = self.clone();
+        self.listen(canvas, "pointerup", move |event| {
+            let Ok(event) = event.dyn_into::<PointerEvent>() else { return };
+            let (x, y) = (event.client_x() as f64, event.client_y() as f64);
+            let direction = runtime.state.borrow_mut().game.swipe.pointer_up(event.pointer_id(), x, y, event.time_stamp());
+            if let Some(direction) = direction { runtime.perform(InputAction::Move(direction)); }
+        })?;
+
+        let runtime = self.clone();
+        self.listen(canvas, "pointercancel", move |event| {
+            let Ok(event) = event.dyn_into::<PointerEvent>() else { return };
+            runtime.state.borrow_mut().game.swipe.pointer_cancel(event.pointer_id());
+        })
+    }
+
+    /// Stops the frame loop, removes the listeners and drops the callbacks
+    /// holding the board. Safe to call from inside one of them.
+    fn destroy(&self) {
+        let mut hooks = self.hooks.borrow_mut();
+        if hooks.destroyed { return; }
+        hooks.destroyed = true;
+        if let (Some(id), Some(window)) = (hooks.frame_id.take(), window()) {
+            let _ = window.cancel_animation_frame(id);
+        }
+        for listener in hooks.listeners.drain(..) {
+            let _ = listener.target.remove_event_listener_with_callback(listener.kind, listener.callback.as_ref().unchecked_ref());
+        }
+        hooks.frame = None;
+        // a page-wide scope is free for another board
+        if let Ok(state) = self.state.try_borrow() { let _ = state.canvas.remove_attribute(KEYBOARD_ATTRIBUTE); }
+    }
+}
+
+/// True for text fields, text areas, selects and contenteditable elements.
+fn is_editable(target: Option<EventTarget>) -> bool {
+    let Some(element) = target.and_then(|t| t.dyn_into::<web_sys::HtmlElement>().ok()) else { return false };
+    matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT") || element.is_content_editable()
+}
+
+/// State of the controller at `index`, or of the first connected one.
+fn read_gamepad(index: Option<u32>) -> Option<PadState> {
+    let pads = window()?.navigator().get_gamepads().ok()?;
+    let pad = match index {
+        Some(index) => pads.get(index).dyn_into::<web_sys::Gamepad>().ok(),
+        None => pads.iter().find_map(|p| p.dyn_into::<web_sys::Gamepad>().ok().filter(|p| p.connected())),
+    }.filter(|p| p.connected())?;
+    let buttons = pad.buttons().iter()
+        .map(|b| b.dyn_into::<web_sys::GamepadButton>().is_ok_and(|b| b.pressed()))
+        .collect();
+    let axes = pad.axes().iter().map(|a| a.as_f64().unwrap_or(0.0)).collect();
+    Some(PadState { buttons, axes })
+}
+
+fn parse_action(action: &str) -> Result<InputAction, JsValue> {
+    action.parse().map_err(|e: String| JsValue::from_str(&e))
+}
+
+/// One board started by `start`, with its own game, canvas, animation,
+/// save slot, keys and controller, so several can share a page.
+#[wasm_bindgen]
+pub struct GameHandle {
+    runtime: Runtime,
+    canvas_id: String,
+}
+
+#[wasm_bindgen]
+impl GameHandle {
+    pub fn canvas_id(&self) -> String { self.canvas_id.clone() }
+
+    /// Unhooks the board from the page: its frame loop stops and its
+    /// listeners are removed, leaving the last frame on the canvas. The
+    /// canvas can then be passed to `start` again; `start` refuses a canvas
+    /// whose board is still live. Changes made through a destroyed handle
+    /// are ignored or refused, so it cannot touch the next board's save.
+    pub fn destroy(&self) {
+        self.runtime.destroy();
+        let settled = self.runtime.state.borrow_mut().anim.take().is_some();
+        if settled { self.runtime.redraw(); }
+    }
+
+    pub fn is_destroyed(&self) -> bool { self.runtime.hooks.borrow().destroyed }
+
+    /// Plays `action` (`"up"`, ..., `"undo"`, `"pause"`, ...) as if its key were pressed.
+    pub fn perform(&self, action: &str) -> Result<(), JsValue> {
+        self.live()?;
+        self.runtime.perform(parse_action(action)?);
+        Ok(())
+    }
+
+    /// Starts over with `config` (a plain object as for `WasmGameService.from_config`),
+    /// or under the current one when undefined. Boards given the same `seed`
+    /// are dealt the same tiles.
+    pub fn new_game(&self, config: JsValue) -> Result<(), JsValue> {
+        self.live()?;
+        let config: Option<GameConfig> = serde_wasm_bindgen::from_value(config)?;
+        if let Some(config) = &config { config.validate().map_err(|e| JsValue::from_str(&e))?; }
+        {
+            let mut state = self.runtime.state.borrow_mut();
+            state.anim = None;
+            state.game.queue.clear();
+            match config {
+                Some(config) => state.game.inner.restart_with(config),
+                None => state.game.reset(),
             }
-        }) as Box<dyn FnMut(KeyboardEvent)>);
+        }
+        self.runtime.persist();
+        self.runtime.redraw();
+        Ok(())
+    }
+
+    pub fn score(&self) -> u32 { self.game(|g| g.score()) }
+    pub fn best_score(&self) -> u32 { self.game(|g| g.best_score()) }
+    pub fn moves(&self) -> u32 { self.game(|g| g.moves()) }
+    pub fn is_over(&self) -> bool { self.game(|g| g.is_over()) }
+    pub fn is_won(&self) -> bool { self.game(|g| g.is_won()) }
+
+    pub fn set_paused(&self, paused: bool) {
+        if self.is_destroyed() { return; }
+        self.game(|g| g.set_paused(paused));
+        self.runtime.redraw();
+    }
+
+    pub fn is_paused(&self) -> bool { self.game(|g| g.is_paused()) }
+    /// See `WasmGameService.autoplay`.
+    pub fn autoplay(&self, enabled: bool, speed: Option<f64>) {
+        if !self.is_destroyed() { self.game(|g| g.autoplay(enabled, speed)); }
+    }
+    pub fn is_autoplaying(&self) -> bool { self.game(|g| g.is_autoplaying()) }
+    pub fn set_animation_ms(&self, ms: f64) -> Result<(), JsValue> { self.live()?; self.game(|g| g.set_animation_ms(ms)) }
+    pub fn animation_ms(&self) -> f64 { self.game(|g| g.animation_ms()) }
+    /// See `WasmGameService.set_move_queue`.
+    pub fn set_move_queue(&self, capacity: usize, fast_forward: Option<bool>) {
+        if !self.is_destroyed() { self.game(|g| g.set_move_queue(capacity, fast_forward)); }
+    }
+    pub fn queued_moves(&self) -> usize { self.game(|g| g.queued_moves()) }
+    /// See `WasmGameService.set_swipe`.
+    pub fn set_swipe(&self, min_distance: Option<f64>, min_velocity: Option<f64>, angle_tolerance: Option<f64>) -> Result<(), JsValue> {
+        self.live()?;
+        self.game(|g| g.set_swipe(min_distance, min_velocity, angle_tolerance))
+    }
+    /// See `WasmGameService.set_gamepad_mapping`.
+    pub fn set_gamepad_mapping(&self, mapping: JsValue) -> Result<(), JsValue> { self.live()?; self.game(|g| g.set_gamepad_mapping(mapping)) }
+    pub fn gamepad_mapping(&self) -> Result<JsValue, JsValue> { self.game(|g| g.gamepad_mapping()) }
+
+    /// Which controller drives this board, by its `Gamepad.index`; undefined
+    /// takes the first connected one. Give each board its own in a vs. view.
+    pub fn set_gamepad_index(&self, index: Option<u32>) {
+        if !self.is_destroyed() { self.runtime.state.borrow_mut().gamepad_index = index; }
+    }
+
+    /// Which key presses this board reads: `"page"` (anywhere on the page
+    /// but another board's canvas), `"focus"` (only while its canvas has
+    /// focus) or `"off"`. The first board started takes `"page"`, later ones `"focus"`.
+    pub fn set_keyboard(&self, scope: &str) -> Result<(), JsValue> {
+        self.live()?;
+        let scope = KeyboardScope::ALL.into_iter().find(|s| s.as_str() == scope)
+            .ok_or_else(|| JsValue::from_str(&format!("Bilinmeyen klavye kapsami: {}", scope)))?;
+        self.runtime.set_keyboard(scope);
+        Ok(())
+    }
+
+    pub fn keyboard(&self) -> String { self.runtime.state.borrow().keyboard.as_str().to_string() }
+
+    pub fn load_ntuple(&self, bytes: &[u8]) -> Result<(), JsValue> { self.live()?; self.game(|g| g.load_ntuple(bytes)) }
+    pub fn use_expectimax(&self) { if !self.is_destroyed() { self.game(|g| g.use_expectimax()); } }
+    pub fn snapshot(&self) -> Result<JsValue, JsValue> { self.game(|g| g.snapshot()) }
+    pub fn to_svg(&self, width: Option<f64>, height: Option<f64>) -> String { self.game(|g| g.to_svg(width, height)) }
+    pub fn replay_json(&self) -> Result<String, JsValue> { self.game(|g| g.replay_json()) }
+    pub fn start_recording(&self) { if !self.is_destroyed() { self.game(|g| g.start_recording()); } }
+    pub fn stop_recording(&self) { if !self.is_destroyed() { self.game(|g| g.stop_recording()); } }
+    /// See `WasmGameService.export_recording`.
+    pub fn export_recording(&self, format: &str, fps: Option<u32>, width: Option<u32>, height: Option<u32>) -> Result<Vec<u8>, JsValue> {
+        self.game(|g| g.export_recording(format, fps, width, height))
+    }
+
+    /// Current key bindings as an object from key code to action, e.g.
+    /// `{ ArrowUp: "up", KeyR: "restart", ... }`.
+    pub fn key_bindings(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.runtime.state.borrow().input).map_err(JsValue::from)
+    }
+
+    /// Replaces every binding at once with an object like the one
+    /// `key_bindings` returns, and saves it.
+    pub fn set_key_bindings(&self, bindings: JsValue) -> Result<(), JsValue> {
+        self.live()?;
+        let map: InputMap = serde_wasm_bindgen::from_value(bindings)?;
+        self.runtime.state.borrow_mut().input = map;
+        self.runtime.persist_input_map();
+        Ok(())
+    }
+
+    /// The action other than `action` that `code` already triggers, if any.
+    pub fn key_conflict(&self, code: &str, action: &str) -> Result<Option<String>, JsValue> {
+        let action = parse_action(action)?;
+        Ok(self.runtime.state.borrow().input.conflict(code, action).map(|a| a.as_str().to_string()))
+    }
+
+    /// Binds the key `code` (a `KeyboardEvent.code`) to `action` and saves the
+    /// bindings. A key already used by another action is refused unless
+    /// `replace` is true, in which case the action it loses is returned.
+    pub fn bind_key(&self, code: &str, action: &str, replace: Option<bool>) -> Result<Option<String>, JsValue> {
+        self.live()?;
+        let action = parse_action(action)?;
+        let replaced = {
+            let map = &mut self.runtime.state.borrow_mut().input;
+            if replace.unwrap_or(false) { Ok(map.rebind(code, action)) } else { map.bind(code, action).map(|()| None) }
+        }.map_err(|e| JsValue::from_str(&e))?;
+        self.runtime.persist_input_map();
+        Ok(replaced.map(|a| a.as_str().to_string()))
+    }
+
+    /// Removes the binding of `code`; returns the action it had.
+    pub fn unbind_key(&self, code: &str) -> Option<String> {
+        if self.is_destroyed() { return None; }
+        let removed = self.runtime.state.borrow_mut().input.unbind(code);
+        self.runtime.persist_input_map();
+        removed.map(|a| a.as_str().to_string())
+    }
+
+    /// Goes back to the default keys (arrows, WASD, HJKL, ...).
+    pub fn reset_key_bindings(&self) {
+        if self.is_destroyed() { return; }
+        self.runtime.state.borrow_mut().input = InputMap::default();
+        self.runtime.persist_input_map();
+    }
+}
 
-        document
-            .add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())?;
-        closure.forget();
+impl GameHandle {
+    /// Errs once `destroy` ran: the save slot and canvas may belong to a new board by then.
+    fn live(&self) -> Result<(), JsValue> {
+        if self.is_destroyed() { Err(JsValue::from_str("Tahta kapatildi")) } else { Ok(()) }
     }
 
-    Ok(())
+    fn game<T>(&self, f: impl FnOnce(&mut WasmGameService) -> T) -> T {
+        f(&mut self.runtime.state.borrow_mut().game)
+    }
 }
 
+/// Starts a board on the canvas `canvas_id`, restoring its save, and
+/// returns its handle. The canvas id doubles as the storage namespace, so
+/// each board keeps its own save, best score and keys.
+#[wasm_bindgen]
+pub fn start(canvas_id: String) -> Result<GameHandle, JsValue> {
+    console_error_panic_hook::set_once();
+    let window = window().ok_or("no window")?;
+    let document = window.document().ok_or("no document")?;
+    let canvas = document.get_element_by_id(&canvas_id).ok_or("no canvas")?
+        .dyn_into::<HtmlCanvasElement>().map_err(|_| "no canvas")?;
+    // a live board keeps the attribute until `destroy`
+    if canvas.has_attribute(KEYBOARD_ATTRIBUTE) { return Err("canvas already running a board".into()); }
+
+    let storage = GameStorage::new(&canvas_id);
+    let mut inner = storage.load_game().map(GameService::from_snapshot).unwrap_or_else(|| GameService::new(4));
+    inner.set_best_score(storage.load_best_score());
+    let input = storage.load_input_map().unwrap_or_default();
+
+    Canvas2DRenderer::from_canvas(&canvas).map_err(|e| JsValue::from_str(&e))?;
+    let instance = Instance {
+        game: WasmGameService::from(inner),
+        canvas: canvas.clone(),
+        anim: None,
+        storage,
+        input,
+        gamepad_index: None,
+        keyboard: KeyboardScope::Off,
+    };
+    let runtime = Runtime { state: Rc::new(RefCell::new(instance)), hooks: Rc::default() };
+    // one board reads the whole page; the others wait for focus
+    let selector = format!("[{}={}]", KEYBOARD_ATTRIBUTE, KeyboardScope::Page.as_str());
+    let taken = document.query_selector(&selector).ok().flatten().is_some();
+    runtime.set_keyboard(if taken { KeyboardScope::Focus } else { KeyboardScope::Page });
+    if !canvas.has_attribute("tabindex") { canvas.set_tab_index(0); }
+    runtime.redraw();
+    runtime.run_frames();
+    if let Err(e) = runtime.listen_for_keys(&document).and_then(|()| runtime.listen_for_swipes(&canvas)) {
+        runtime.destroy();
+        return Err(e);
+    }
+    Ok(GameHandle { runtime, canvas_id })
+}
@@ -1,6 +1,32 @@
 mod domain;
 mod application;
 mod infra;
+mod ai;
 
+pub use ai::expectimax::{Expectimax, SearchBudget};
+pub use ai::heuristic::{Heuristic, WeightedHeuristic};
+pub use ai::montecarlo::{MonteCarlo, RolloutPolicy};
+pub use ai::ntuple::NTupleNetwork;
+pub use ai::simple::{CornerPlayer, GreedyPlayer, RandomPlayer};
+pub use ai::strategy::{strategy_by_name, Strategy, StrategyOptions, STRATEGY_NAMES};
 pub use application::game_service::GameService;
+pub use application::recording::{RecordedStep, Recording};
+pub use application::replay::{Replay, ReplayAction, ReplayFrame, ReplayLog, ReplayPlayer, ReplayStep, MAX_REPLAY_STEPS, REPLAY_VERSION};
+pub use application::snapshot::GameSnapshot;
+pub use domain::bitboard::{BitBoard, MAX_BITBOARD_TILE};
+pub use domain::board::{Board, MoveEvent, MoveOutcome, SpawnEvent, Tile, MAX_TILE};
+pub use domain::config::GameConfig;
+pub use domain::direction::Direction;
+pub use domain::rng::GameRng;
+pub use domain::spawn::{SpawnPolicy, SpawnRules, MAX_SPAWN_VALUE};
+pub use infra::export::{export_recording, AnimationFormat, ExportOptions, MAX_EXPORT_FRAMES, MAX_EXPORT_SIZE};
+pub use infra::gamepad::{GamepadInput, GamepadMapping, PadState};
+pub use infra::gesture::{SwipeConfig, SwipeDetector};
+pub use infra::input::{InputAction, InputMap};
+pub use infra::layout::{animated_sprites, ease_out_cubic, static_sprites, Layout, Rect, Sprite, MOVE_ANIMATION_MS};
+pub use infra::move_queue::MoveQueue;
+pub use infra::palette;
+pub use infra::raster::RasterSurface;
+pub use infra::surface::{DrawSurface, Hud, Renderer, TextAlign, TextStyle};
+pub use infra::svg::{board_svg, frame_svg, SvgSurface};
 pub use infra::wasm_bindings::*;
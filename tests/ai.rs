@@ -0,0 +1,135 @@
+mod common;
+
+use common::{random_board, DIRECTIONS};
+use game_2048::{
+    strategy_by_name, Board, Heuristic, CornerPlayer, Expectimax, GameConfig, GreedyPlayer, MonteCarlo, NTupleNetwork, RandomPlayer,
+    RolloutPolicy, SearchBudget, SpawnPolicy, SpawnRules, Strategy, StrategyOptions, Tile, WeightedHeuristic, STRATEGY_NAMES,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn search(depth: u32) -> Expectimax {
+    Expectimax::new(Box::new(WeightedHeuristic::default()), SearchBudget { max_depth: depth, time_ms: None })
+}
+
+fn strategies() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(search(2)),
+        Box::new(MonteCarlo::new(4, RolloutPolicy::Random, None).with_seed(1)),
+        Box::new(MonteCarlo::new(4, RolloutPolicy::Greedy, None).with_seed(2)),
+        Box::new(RandomPlayer::new(3)),
+        Box::new(GreedyPlayer),
+        Box::new(CornerPlayer),
+    ]
+}
+
+#[test]
+fn best_move_is_always_a_legal_move() {
+    let mut rng = StdRng::seed_from_u64(11);
+    for mut ai in strategies() {
+        for (rows, cols) in [(4, 4), (3, 5), (2, 2)] {
+            for _ in 0..50 {
+                let board = random_board(&mut rng, rows, cols);
+                match ai.choose(&board) {
+                    Some((direction, _)) => assert!(board.clone().shift(direction).moved, "{} chose {:?} on {:?}", ai.name(), direction, board.cells),
+                    None => assert!(DIRECTIONS.iter().all(|&d| !board.clone().shift(d).moved), "{} gave up on {:?}", ai.name(), board.cells),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn no_move_on_a_stuck_board() {
+    let mut board = Board::from_config(&GameConfig { seed: Some(1), ..GameConfig::rect(2, 2) });
+    for (i, value) in [2, 4, 4, 2].into_iter().enumerate() {
+        board.set(i / 2, i % 2, Some(Tile::new(value)));
+    }
+    for mut ai in strategies() {
+        assert_eq!(ai.choose(&board), None, "{}", ai.name());
+    }
+}
+
+#[test]
+fn seeded_monte_carlo_is_reproducible() {
+    let board = Board::from_config(&GameConfig { seed: Some(5), ..GameConfig::new(4) });
+    let pick = || MonteCarlo::new(8, RolloutPolicy::Random, None).with_seed(9).best_move(&board);
+    assert_eq!(pick(), pick());
+}
+
+#[test]
+fn time_budget_still_returns_a_move() {
+    let board = Board::from_config(&GameConfig { seed: Some(3), ..GameConfig::new(4) });
+    let mut ai = Expectimax::new(Box::new(WeightedHeuristic::default()), SearchBudget { max_depth: 10, time_ms: Some(0.0) });
+    assert!(ai.best_move(&board).is_some());
+
+    // one rollout on a large board takes far longer than the budget
+    let board = Board::from_config(&GameConfig { seed: Some(3), ..GameConfig::new(16) });
+    let mut ai = MonteCarlo::new(1_000, RolloutPolicy::Random, Some(20.0)).with_seed(4);
+    let started = std::time::Instant::now();
+    assert!(ai.best_move(&board).is_some());
+    assert!(started.elapsed().as_millis() < 200, "took {:?}", started.elapsed());
+}
+
+#[test]
+fn game_service_search_respects_the_given_budget() {
+    let mut game = game_2048::GameService::with_seed(4, 8);
+    let quick = SearchBudget { max_depth: 3, time_ms: Some(0.0) };
+    let (direction, _) = game.best_move_within(quick).unwrap();
+    assert!(game.board().clone().shift(direction).moved);
+    let fixed = game.best_move_within(SearchBudget { max_depth: 2, time_ms: None });
+    assert_eq!(fixed.map(|(d, _)| d), search(2).best_move(game.board()).map(|(d, _)| d));
+}
+
+#[test]
+fn strategies_are_built_by_name() {
+    let options = StrategyOptions { ntuple: Some(NTupleNetwork::default()), ..StrategyOptions::default() };
+    for name in STRATEGY_NAMES {
+        assert_eq!(strategy_by_name(name, &options).unwrap().name(), name);
+    }
+    assert!(strategy_by_name("ntuple", &StrategyOptions::default()).is_err());
+    assert!(strategy_by_name("minimax", &options).is_err());
+}
+
+/// Expected evaluation over `spawns` more tiles on a 3x3 corner-biased board,
+/// with the cell weights written out: 3 in a corner, 2 on an edge, 1 in the middle.
+fn expected_3x3(board: &Board, spawns: usize) -> f64 {
+    let empties = board.empty_positions();
+    if spawns == 0 || empties.is_empty() { return WeightedHeuristic::default().evaluate(board); }
+    let weight = |(r, c): (usize, usize)| [[3.0, 2.0, 3.0], [2.0, 1.0, 2.0], [3.0, 2.0, 3.0]][r][c];
+    let total: f64 = empties.iter().map(|&pos| weight(pos)).sum();
+    empties.iter().map(|&(r, c)| {
+        [(2, 0.25), (4, 0.75)].iter().map(|&(value, p)| {
+            let mut child = board.clone();
+            child.set(r, c, Some(Tile::new(value)));
+            p * expected_3x3(&child, spawns - 1)
+        }).sum::<f64>() * weight((r, c)) / total
+    }).sum()
+}
+
+#[test]
+fn chance_nodes_follow_the_spawn_rules() {
+    let spawn = SpawnRules { values: vec![(2, 1), (4, 3)], per_move: 2, policy: SpawnPolicy::CornerBiased, ..SpawnRules::default() };
+    let mut board = Board::from_config(&GameConfig { seed: Some(8), spawn, ..GameConfig::new(3) });
+    board.apply_move(DIRECTIONS[0]);
+    let expected = DIRECTIONS.iter()
+        .filter_map(|&d| {
+            let mut next = board.clone();
+            next.shift(d).moved.then(|| expected_3x3(&next, 2))
+        })
+        .fold(f64::MIN, f64::max);
+    let (_, score) = search(1).best_move(&board).unwrap();
+    assert!((score - expected).abs() < 1e-9, "{} vs {}", score, expected);
+
+    // without spawns a move is worth the board it leaves
+    let spawn = SpawnRules { per_move: 0, ..SpawnRules::default() };
+    let board = Board::from_config(&GameConfig { seed: Some(8), spawn, ..GameConfig::new(4) });
+    let expected = DIRECTIONS.iter()
+        .filter_map(|&d| {
+            let mut next = board.clone();
+            next.shift(d).moved.then(|| WeightedHeuristic::default().evaluate(&next))
+        })
+        .fold(f64::MIN, f64::max);
+    let (_, score) = search(1).best_move(&board).unwrap();
+    assert!((score - expected).abs() < 1e-9, "{} vs {}", score, expected);
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{random_board, DIRECTIONS};
+use game_2048::{BitBoard, Board, GameConfig, Heuristic, SpawnPolicy, SpawnRules, Tile, WeightedHeuristic};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn bitboard_roundtrips_through_board() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..1_000 {
+        let board = random_board(&mut rng, 4, 4);
+        let bits = BitBoard::try_from(&board).unwrap();
+        let mut back = board.clone();
+        back.cells = vec![None; 16];
+        bits.apply_to(&mut back);
+        assert_eq!(back, board);
+    }
+}
+
+#[test]
+fn bitboard_rejects_unsupported_boards() {
+    assert!(BitBoard::try_from(&Board::with_seed(5, 0)).is_err());
+    let mut board = Board::with_seed(4, 0);
+    board.set(0, 0, Some(Tile::new(65536)));
+    assert!(BitBoard::try_from(&board).is_err());
+}
+
+#[test]
+fn bitboard_and_board_engines_agree() {
+    let mut rng = StdRng::seed_from_u64(2048);
+    for _ in 0..20_000 {
+        let board = random_board(&mut rng, 4, 4);
+        let bits = BitBoard::try_from(&board).unwrap();
+        assert_eq!(bits.can_move(), board.can_move());
+        assert_eq!(bits.empty_count() as usize, board.empty_positions().len());
+
+        for direction in DIRECTIONS {
+            let mut expected = board.clone();
+            let moved = expected.shift(direction).moved;
+            let (slid, gained) = bits.slide(direction);
+
+            assert_eq!(slid != bits, moved, "{:?} on {:?}", direction, board.cells);
+            assert_eq!(gained, expected.score - board.score);
+            assert_eq!(slid, BitBoard::try_from(&expected).unwrap());
+        }
+    }
+}
+
+#[test]
+fn bitboard_spawns_and_evaluates_like_board() {
+    let spawn = SpawnRules { values: vec![(2, 3), (4, 1), (8, 1)], per_move: 2, policy: SpawnPolicy::CornerBiased, ..SpawnRules::default() };
+    let heuristic = WeightedHeuristic::default();
+    let mut board = Board::from_config(&GameConfig { seed: Some(9), spawn, ..GameConfig::new(4) });
+    for direction in DIRECTIONS.iter().cycle().take(200) {
+        let bits = BitBoard::try_from(&board).unwrap();
+        assert!((heuristic.evaluate(&board) - heuristic.evaluate_bits(bits)).abs() < 1e-9);
+
+        let mut rng = board.rng.clone();
+        let (mut slid, _) = bits.slide(*direction);
+        if !board.apply_move(*direction).moved { continue; }
+        slid.spawn_tiles(&board.spawn, board.spawn.per_move, &mut rng);
+        assert_eq!(slid, BitBoard::try_from(&board).unwrap());
+        if !board.can_move() { break; }
+    }
+}
@@ -0,0 +1,27 @@
+// every test crate compiles this module but uses only some of it
+#![allow(dead_code)]
+
+use game_2048::{Board, Direction, GameConfig, Tile};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+pub const DIRECTIONS: [Direction; 4] = [Direction::Left, Direction::Right, Direction::Up, Direction::Down];
+
+/// A seeded board with a random fill level and tiles from 2 to 2048.
+pub fn random_board(rng: &mut StdRng, rows: usize, cols: usize) -> Board {
+    let config = GameConfig { seed: Some(rng.gen_range(0..u64::MAX)), ..GameConfig::rect(rows, cols) };
+    let mut board = Board::from_config(&config);
+    let cells = rows * cols;
+    let fill = rng.gen_range(0..=cells);
+    for row in 0..rows {
+        for col in 0..cols {
+            let tile = if rng.gen_range(0..cells) < fill {
+                Some(Tile::new(1 << rng.gen_range(1..=11)))
+            } else {
+                None
+            };
+            board.set(row, col, tile);
+        }
+    }
+    board
+}
@@ -0,0 +1,50 @@
+use game_2048::{Direction, GameConfig, GameService};
+
+const DIRECTIONS: [Direction; 4] = [Direction::Left, Direction::Down, Direction::Right, Direction::Up];
+
+#[test]
+fn win_target_must_be_a_reachable_power_of_two() {
+    // 4 already spawns with the default table
+    for target in [0, 1, 2, 3, 4, 100, 2047] {
+        let config = GameConfig { win_target: target, ..GameConfig::new(4) };
+        assert!(config.validate().is_err(), "{} accepted", target);
+    }
+    for target in [8, 512, 2048, 1 << 17] {
+        assert!(GameConfig { win_target: target, ..GameConfig::new(4) }.validate().is_ok());
+    }
+}
+
+#[test]
+fn winning_blocks_moves_until_continue() {
+    let mut game = GameService::with_config(GameConfig { seed: Some(5), win_target: 16, ..GameConfig::new(4) });
+    assert!(!game.win_pending());
+    let mut directions = DIRECTIONS.iter().cycle();
+    while !game.win_pending() {
+        assert!(!game.is_over(), "lost before reaching 16");
+        game.apply_move(*directions.next().unwrap());
+    }
+    assert!(game.is_won());
+
+    let board = game.board().clone();
+    for direction in DIRECTIONS {
+        assert!(!game.apply_move(direction).moved);
+    }
+    assert_eq!(game.board().cells, board.cells);
+
+    game.keep_playing();
+    assert!(!game.win_pending());
+    assert!(DIRECTIONS.iter().any(|d| game.apply_move(*d).moved));
+
+    // a new game has to be won again
+    game.reset();
+    assert!(!game.win_pending() && !game.is_keep_playing());
+}
+
+#[test]
+fn board_dimensions_are_bounded() {
+    assert!(GameConfig::rect(16, 16).validate().is_ok());
+    assert!(GameConfig::rect(1, 2).validate().is_ok());
+    for (rows, cols) in [(0, 4), (1, 1), (17, 4), (4, 17), (1 << 33, 1 << 33), (usize::MAX, 2)] {
+        assert!(GameConfig::rect(rows, cols).validate().is_err(), "{}x{} accepted", rows, cols);
+    }
+}
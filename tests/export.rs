@@ -0,0 +1,86 @@
+use game_2048::{export_recording, AnimationFormat, Direction, ExportOptions, GameConfig, GameService, Recording, MAX_EXPORT_FRAMES, MAX_EXPORT_SIZE};
+
+const DIRECTIONS: [Direction; 4] = [Direction::Left, Direction::Down, Direction::Right, Direction::Up];
+
+/// A short seeded game with one undo in the middle.
+fn recorded_game() -> Recording {
+    let mut game = GameService::with_config(GameConfig { seed: Some(19), ..GameConfig::new(4) });
+    game.start_recording();
+    for direction in DIRECTIONS.iter().cycle().take(8) {
+        game.apply_move(*direction);
+    }
+    game.undo();
+    game.stop_recording().unwrap()
+}
+
+fn options() -> ExportOptions {
+    ExportOptions { width: 120, height: 150, fps: 10, ..ExportOptions::default() }
+}
+
+#[test]
+fn recording_tracks_moves_and_cuts() {
+    let recording = recorded_game();
+    let moves = recording.steps.iter().filter(|s| s.direction.is_some()).count();
+    assert!(moves > 0);
+    let last = recording.steps.last().unwrap();
+    assert_eq!(last.direction, None);
+    assert_eq!(recording.final_board(), &last.board);
+    for step in recording.steps.iter().filter(|s| s.direction.is_some()) {
+        assert!(step.outcome.moved);
+    }
+}
+
+#[test]
+fn gif_has_a_frame_per_animation_step() {
+    let recording = recorded_game();
+    let bytes = export_recording(&recording, AnimationFormat::Gif, &options()).unwrap();
+    assert!(bytes.starts_with(b"GIF89a"));
+
+    let mut decoder = gif::DecodeOptions::new().read_info(bytes.as_slice()).unwrap();
+    let mut frames = 0;
+    while decoder.read_next_frame().unwrap().is_some() {
+        frames += 1;
+    }
+    // 140ms at 10fps is two frames per move, plus a settled frame per step and the first board
+    let moves = recording.steps.iter().filter(|s| s.direction.is_some()).count();
+    assert_eq!(frames, 1 + recording.steps.len() + 2 * moves);
+}
+
+#[test]
+fn apng_is_animated() {
+    let recording = recorded_game();
+    let bytes = export_recording(&recording, AnimationFormat::Apng, &options()).unwrap();
+    let decoder = png::Decoder::new(bytes.as_slice());
+    let reader = decoder.read_info().unwrap();
+    let info = reader.info();
+    assert_eq!((info.width, info.height), (120, 150));
+    let moves = recording.steps.iter().filter(|s| s.direction.is_some()).count();
+    assert_eq!(info.animation_control().unwrap().num_frames as usize, 1 + recording.steps.len() + 2 * moves);
+}
+
+#[test]
+fn rejects_bad_options() {
+    let recording = recorded_game();
+    let bad = ExportOptions { fps: 0, ..options() };
+    assert!(export_recording(&recording, AnimationFormat::Gif, &bad).is_err());
+    // refused before a single frame is allocated
+    let huge = ExportOptions { width: 65535, height: 65535, ..options() };
+    assert!(export_recording(&recording, AnimationFormat::Apng, &huge).is_err());
+    assert!(ExportOptions { width: MAX_EXPORT_SIZE, height: MAX_EXPORT_SIZE, ..options() }.validate().is_ok());
+    assert!(ExportOptions { height: MAX_EXPORT_SIZE + 1, ..options() }.validate().is_err());
+    assert!("webp".parse::<AnimationFormat>().is_err());
+
+    for ms in [f64::INFINITY, f64::NAN, -1.0, 1e12] {
+        assert!(ExportOptions { move_ms: ms, ..options() }.validate().is_err(), "move_ms {}", ms);
+        assert!(ExportOptions { hold_ms: ms, ..options() }.validate().is_err(), "hold_ms {}", ms);
+    }
+    // a minute per move at 100 fps is 6000 frames a move
+    let mut game = GameService::with_config(GameConfig { seed: Some(19), ..GameConfig::new(4) });
+    game.start_recording();
+    for direction in DIRECTIONS.iter().cycle().take(20) {
+        game.apply_move(*direction);
+    }
+    let slow = ExportOptions { move_ms: 60_000.0, fps: 100, ..options() };
+    assert!(slow.validate().is_ok());
+    assert!(export_recording(&game.stop_recording().unwrap(), AnimationFormat::Gif, &slow).unwrap_err().contains(&MAX_EXPORT_FRAMES.to_string()));
+}
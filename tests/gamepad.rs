@@ -0,0 +1,68 @@
+use game_2048::{Direction, GamepadInput, GamepadMapping, InputAction, PadState};
+
+fn pressed(buttons: &[usize]) -> PadState {
+    let mut state = PadState { buttons: vec![false; 17], axes: vec![0.0; 4] };
+    for &b in buttons {
+        state.buttons[b] = true;
+    }
+    state
+}
+
+fn stick(x: f64, y: f64) -> PadState {
+    PadState { axes: vec![x, y, 0.0, 0.0], ..pressed(&[]) }
+}
+
+#[test]
+fn dpad_moves_once_then_repeats_while_held() {
+    let mut input = GamepadInput::default();
+    let left = InputAction::Move(Direction::Left);
+    assert_eq!(input.update(&pressed(&[14]), 0.0), vec![left]);
+    assert!(input.update(&pressed(&[14]), 100.0).is_empty());
+    assert_eq!(input.update(&pressed(&[14]), 300.0), vec![left]);
+    assert!(input.update(&pressed(&[14]), 400.0).is_empty());
+    assert_eq!(input.update(&pressed(&[14]), 450.0), vec![left]);
+
+    assert!(input.update(&pressed(&[]), 500.0).is_empty());
+    assert_eq!(input.update(&pressed(&[14]), 510.0), vec![left]);
+}
+
+#[test]
+fn stick_respects_the_dead_zone() {
+    let mut input = GamepadInput::default();
+    assert!(input.update(&stick(0.3, -0.4), 0.0).is_empty());
+    assert_eq!(input.update(&stick(0.2, -0.9), 10.0), vec![InputAction::Move(Direction::Up)]);
+    assert_eq!(input.update(&stick(0.8, 0.1), 20.0), vec![InputAction::Move(Direction::Right)]);
+    assert!(input.update(&stick(0.0, 0.0), 30.0).is_empty());
+    assert_eq!(input.update(&stick(0.0, 0.7), 40.0), vec![InputAction::Move(Direction::Down)]);
+}
+
+#[test]
+fn face_buttons_fire_on_press_only() {
+    let mut input = GamepadInput::default();
+    assert_eq!(input.update(&pressed(&[1]), 0.0), vec![InputAction::Undo]);
+    assert!(input.update(&pressed(&[1]), 1000.0).is_empty());
+    assert_eq!(input.update(&pressed(&[1, 3]), 1010.0), vec![InputAction::Restart]);
+    assert!(input.update(&pressed(&[]), 1020.0).is_empty());
+    assert_eq!(input.update(&pressed(&[1]), 1030.0), vec![InputAction::Undo]);
+}
+
+#[test]
+fn mapping_is_configurable() {
+    let mapping: GamepadMapping = serde_json::from_str(
+        r#"{ "buttons": [[0, "up"], [5, "redo"]], "stick": null, "repeat_interval_ms": 0 }"#,
+    ).unwrap();
+    assert!(mapping.validate().is_ok());
+    assert_eq!(mapping.dead_zone, GamepadMapping::default().dead_zone);
+
+    let mut input = GamepadInput::new(mapping);
+    assert!(input.update(&stick(1.0, 0.0), 0.0).is_empty());
+    assert_eq!(input.update(&pressed(&[0]), 10.0), vec![InputAction::Move(Direction::Up)]);
+    assert!(input.update(&pressed(&[0]), 5000.0).is_empty(), "repeat is off");
+    assert_eq!(input.update(&pressed(&[5]), 5010.0), vec![InputAction::Redo]);
+    assert!(input.update(&pressed(&[14]), 5020.0).is_empty(), "the D-pad is unmapped");
+
+    assert!(serde_json::from_str::<GamepadMapping>(r#"{ "buttons": [[0, "jump"]] }"#).is_err());
+    for dead_zone in [1.5, 1.0, 0.0, -0.1, f64::NAN] {
+        assert!(GamepadMapping { dead_zone, ..GamepadMapping::default() }.validate().is_err(), "{}", dead_zone);
+    }
+}
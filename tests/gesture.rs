@@ -0,0 +1,55 @@
+use game_2048::{Direction, SwipeConfig, SwipeDetector};
+
+fn swipe(detector: &mut SwipeDetector, dx: f64, dy: f64, duration_ms: f64) -> Option<Direction> {
+    detector.pointer_down(1, 100.0, 100.0, 1000.0);
+    detector.pointer_up(1, 100.0 + dx, 100.0 + dy, 1000.0 + duration_ms)
+}
+
+#[test]
+fn swipes_map_to_directions() {
+    let mut detector = SwipeDetector::default();
+    assert_eq!(swipe(&mut detector, 80.0, 10.0, 100.0), Some(Direction::Right));
+    assert_eq!(swipe(&mut detector, -80.0, -10.0, 100.0), Some(Direction::Left));
+    assert_eq!(swipe(&mut detector, 5.0, 80.0, 100.0), Some(Direction::Down));
+    assert_eq!(swipe(&mut detector, -5.0, -80.0, 100.0), Some(Direction::Up));
+}
+
+#[test]
+fn short_slow_or_diagonal_strokes_are_ignored() {
+    let mut detector = SwipeDetector::new(SwipeConfig { min_distance: 40.0, min_velocity: 0.5, angle_tolerance: 20.0 });
+    assert_eq!(swipe(&mut detector, 30.0, 0.0, 20.0), None, "too short");
+    assert_eq!(swipe(&mut detector, 100.0, 0.0, 400.0), None, "too slow");
+    assert_eq!(swipe(&mut detector, 100.0, 50.0, 100.0), None, "too far off the axis");
+    assert_eq!(swipe(&mut detector, 100.0, 30.0, 100.0), Some(Direction::Right));
+    assert_eq!(swipe(&mut detector, 0.0, 0.0, 100.0), None, "a tap");
+}
+
+#[test]
+fn second_pointer_cancels_the_gesture() {
+    let mut detector = SwipeDetector::default();
+    detector.pointer_down(1, 0.0, 0.0, 0.0);
+    detector.pointer_down(2, 50.0, 50.0, 10.0);
+    assert!(!detector.is_tracking());
+    assert_eq!(detector.pointer_up(1, 200.0, 0.0, 100.0), None);
+
+    // the remaining finger cannot start a swipe until it is lifted too
+    assert_eq!(detector.pointer_up(2, 50.0, 250.0, 120.0), None);
+    assert_eq!(swipe(&mut detector, 0.0, 80.0, 100.0), Some(Direction::Down));
+}
+
+#[test]
+fn cancelled_pointer_drops_its_gesture() {
+    let mut detector = SwipeDetector::default();
+    detector.pointer_down(7, 0.0, 0.0, 0.0);
+    detector.pointer_cancel(7);
+    assert_eq!(detector.pointer_up(7, 200.0, 0.0, 100.0), None);
+    assert_eq!(swipe(&mut detector, -80.0, 0.0, 100.0), Some(Direction::Left));
+}
+
+#[test]
+fn config_is_validated() {
+    assert!(SwipeConfig::default().validate().is_ok());
+    assert!(SwipeConfig { angle_tolerance: 60.0, ..SwipeConfig::default() }.validate().is_err());
+    assert!(SwipeConfig { min_distance: -1.0, ..SwipeConfig::default() }.validate().is_err());
+    assert!(SwipeConfig { min_velocity: f64::NAN, ..SwipeConfig::default() }.validate().is_err());
+}
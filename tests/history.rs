@@ -0,0 +1,70 @@
+mod common;
+
+use common::DIRECTIONS;
+use game_2048::GameService;
+
+/// Makes `count` moves that change the board.
+fn play(game: &mut GameService, count: usize) {
+    for _ in 0..count {
+        assert!(DIRECTIONS.iter().any(|d| game.slide(*d)), "the game got stuck");
+    }
+}
+
+fn undo_all(game: &mut GameService) -> usize {
+    let mut steps = 0;
+    while game.undo() { steps += 1; }
+    steps
+}
+
+#[test]
+fn depth_zero_disables_undo() {
+    let mut game = GameService::with_seed(4, 1);
+    game.set_undo_depth(Some(0));
+    play(&mut game, 5);
+    assert!(!game.can_undo());
+    assert!(!game.undo());
+    assert_eq!(game.moves(), 5);
+}
+
+#[test]
+fn history_is_trimmed_at_the_limit() {
+    let mut game = GameService::with_seed(4, 2);
+    game.set_undo_depth(Some(3));
+    play(&mut game, 8);
+    assert_eq!(undo_all(&mut game), 3);
+    assert_eq!(game.moves(), 5);
+}
+
+#[test]
+fn shrinking_the_limit_drops_the_oldest_steps() {
+    let mut game = GameService::with_seed(4, 3);
+    game.set_undo_depth(None);
+    play(&mut game, 6);
+    assert!(game.undo());
+    assert!(game.redo());
+
+    game.set_undo_depth(Some(2));
+    assert_eq!(undo_all(&mut game), 2);
+    assert_eq!(game.moves(), 4);
+
+    // going to zero also forgets redo
+    game.set_undo_depth(Some(0));
+    assert!(!game.can_redo());
+}
+
+#[test]
+fn new_move_clears_redo() {
+    let mut game = GameService::with_seed(4, 4);
+    play(&mut game, 4);
+    let latest = game.board().clone();
+    assert!(game.undo());
+    assert!(game.can_redo());
+    assert!(game.redo());
+    assert_eq!(game.board().cells, latest.cells);
+    assert_eq!(game.board().score, latest.score);
+
+    assert!(game.undo());
+    play(&mut game, 1);
+    assert!(!game.can_redo());
+    assert!(!game.redo());
+}
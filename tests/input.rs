@@ -0,0 +1,72 @@
+use game_2048::{Direction, InputAction, InputMap};
+
+#[test]
+fn defaults_cover_arrows_wasd_and_hjkl() {
+    let map = InputMap::default();
+    for (codes, direction) in [
+        (["ArrowUp", "KeyW", "KeyK"], Direction::Up),
+        (["ArrowDown", "KeyS", "KeyJ"], Direction::Down),
+        (["ArrowLeft", "KeyA", "KeyH"], Direction::Left),
+        (["ArrowRight", "KeyD", "KeyL"], Direction::Right),
+    ] {
+        for code in codes {
+            assert_eq!(map.action(code), Some(InputAction::Move(direction)), "{}", code);
+        }
+    }
+    for action in [InputAction::Restart, InputAction::Undo, InputAction::Hint, InputAction::Pause] {
+        assert!(!map.keys(action).is_empty(), "{:?} has no key", action);
+    }
+    assert_eq!(map.action("KeyQ"), None);
+}
+
+#[test]
+fn binding_a_used_key_is_a_conflict() {
+    let mut map = InputMap::default();
+    assert_eq!(map.conflict("KeyW", InputAction::Hint), Some(InputAction::Move(Direction::Up)));
+    let err = map.bind("KeyW", InputAction::Hint).unwrap_err();
+    assert!(err.contains("KeyW"), "{}", err);
+    assert_eq!(map.action("KeyW"), Some(InputAction::Move(Direction::Up)));
+
+    // binding a key to what it already does is fine
+    assert!(map.bind("KeyW", InputAction::Move(Direction::Up)).is_ok());
+    assert!(map.bind("KeyQ", InputAction::Hint).is_ok());
+    assert_eq!(map.keys(InputAction::Hint), vec!["KeyI", "KeyQ"]);
+}
+
+#[test]
+fn rebind_and_unbind() {
+    let mut map = InputMap::default();
+    assert_eq!(map.rebind("KeyW", InputAction::Hint), Some(InputAction::Move(Direction::Up)));
+    assert_eq!(map.action("KeyW"), Some(InputAction::Hint));
+    assert_eq!(map.rebind("KeyW", InputAction::Hint), None);
+
+    assert_eq!(map.unbind("KeyW"), Some(InputAction::Hint));
+    assert_eq!(map.action("KeyW"), None);
+
+    map.clear(InputAction::Move(Direction::Left));
+    assert!(map.keys(InputAction::Move(Direction::Left)).is_empty());
+}
+
+#[test]
+fn bindings_roundtrip_through_json() {
+    let mut map = InputMap::empty();
+    map.bind("Space", InputAction::Pause).unwrap();
+    map.bind("ArrowUp", InputAction::Move(Direction::Up)).unwrap();
+    let json = map.to_json().unwrap();
+    assert_eq!(json, r#"{"ArrowUp":"up","Space":"pause"}"#);
+    assert_eq!(InputMap::from_json(&json).unwrap(), map);
+
+    assert!(InputMap::from_json(r#"{"KeyX":"fly"}"#).is_err());
+    for action in InputAction::ALL {
+        assert_eq!(action.as_str().parse::<InputAction>(), Ok(action));
+    }
+}
+
+#[test]
+fn only_moves_and_history_repeat() {
+    let repeating: Vec<InputAction> = InputAction::ALL.into_iter().filter(|a| a.repeats()).collect();
+    assert_eq!(repeating.len(), 6);
+    for action in [InputAction::Pause, InputAction::Autoplay, InputAction::Restart, InputAction::Continue, InputAction::Hint] {
+        assert!(!action.repeats(), "{:?}", action);
+    }
+}
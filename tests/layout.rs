@@ -0,0 +1,119 @@
+mod common;
+
+use common::{random_board, DIRECTIONS};
+use game_2048::{
+    animated_sprites, ease_out_cubic, static_sprites, Board, DrawSurface, GameConfig, Hud, Layout, Rect, Renderer, TextStyle,
+    Tile,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Records what a renderer asks for instead of drawing it.
+#[derive(Default)]
+struct Recorder {
+    rects: Vec<(Rect, String)>,
+    texts: Vec<(String, f64, f64)>,
+}
+
+impl DrawSurface for Recorder {
+    fn size(&self) -> (f64, f64) { (400.0, 440.0) }
+
+    fn fill_rect(&mut self, rect: Rect, color: &str, _opacity: f64) {
+        self.rects.push((rect, color.to_string()));
+    }
+
+    fn text(&mut self, text: &str, x: f64, y: f64, _style: TextStyle) {
+        self.texts.push((text.to_string(), x, y));
+    }
+}
+
+#[test]
+fn grid_fits_the_surface() {
+    for (rows, cols) in [(4, 4), (3, 5), (6, 2), (1, 4), (8, 8)] {
+        for (w, h) in [(400.0, 440.0), (800.0, 300.0), (250.0, 900.0)] {
+            let layout = Layout::new(rows, cols, w, h);
+            let grid = layout.grid();
+            assert!(grid.w <= w + 1e-9 && grid.h + 40.0 <= h + 1e-9, "{}x{} on {}x{}", rows, cols, w, h);
+            let last = layout.cell((cols - 1) as f64, (rows - 1) as f64, 1.0);
+            assert!((last.x + last.w + layout.pad - grid.w).abs() < 1e-9);
+            assert!((last.y + last.h + layout.pad - grid.h).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn scaled_cells_keep_their_centre() {
+    let layout = Layout::new(4, 4, 400.0, 440.0);
+    let full = layout.cell(1.5, 2.0, 1.0);
+    let popped = layout.cell(1.5, 2.0, 1.15);
+    assert_eq!(full.center(), popped.center());
+    assert!((popped.w - full.w * 1.15).abs() < 1e-9);
+}
+
+#[test]
+fn easing_runs_from_zero_to_one() {
+    assert_eq!(ease_out_cubic(0.0), 0.0);
+    assert_eq!(ease_out_cubic(1.0), 1.0);
+    let samples: Vec<f64> = (0..=20).map(|i| ease_out_cubic(i as f64 / 20.0)).collect();
+    assert!(samples.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn animation_starts_at_the_old_board_and_ends_at_the_new_one() {
+    let mut rng = StdRng::seed_from_u64(17);
+    for _ in 0..500 {
+        let before = random_board(&mut rng, 4, 4);
+        for direction in DIRECTIONS {
+            let mut after = before.clone();
+            let outcome = after.apply_move(direction);
+            if !outcome.moved { continue; }
+
+            // at 0 the moving tiles sit where they were and spawns are not shown yet
+            let start = animated_sprites(&after, &outcome, 0.0);
+            let mut shown: Vec<(u32, usize, usize)> = start.iter().map(|s| (s.value, s.grid_y as usize, s.grid_x as usize)).collect();
+            let mut expected: Vec<(u32, usize, usize)> =
+                static_sprites(&before).iter().map(|s| (s.value, s.grid_y as usize, s.grid_x as usize)).collect();
+            shown.sort();
+            expected.sort();
+            assert_eq!(shown, expected, "{:?} on {:?}", direction, before.cells);
+
+            // at 1 the topmost sprite of every cell shows the new board
+            let end = animated_sprites(&after, &outcome, 1.0);
+            for tile in static_sprites(&after) {
+                let top = end.iter().rev().find(|s| s.grid_x == tile.grid_x && s.grid_y == tile.grid_y).unwrap();
+                assert_eq!(top.value, tile.value);
+            }
+        }
+    }
+}
+
+#[test]
+fn renderer_draws_every_tile_and_the_scores() {
+    let mut board = Board::from_config(&GameConfig { seed: Some(2), ..GameConfig::rect(3, 5) });
+    board.set(0, 0, Some(Tile::new(2048)));
+    let hud = Hud { score: 1234, best_score: 5678, win_pending: true, paused: false };
+    let mut surface = Recorder::default();
+    surface.render(&board, &hud);
+
+    let tiles = board.cells.iter().flatten().count();
+    // background, empty cells, tiles, overlay
+    assert_eq!(surface.rects.len(), 1 + 15 + tiles + 1);
+    let texts: Vec<&str> = surface.texts.iter().map(|(t, _, _)| t.as_str()).collect();
+    assert!(texts.contains(&"2048"));
+    assert!(texts.contains(&"Skor: 1234"));
+    assert!(texts.contains(&"En iyi: 5678"));
+    assert!(texts.contains(&"Kazandın!"));
+    assert!(texts.contains(&"Devam et ya da yeni oyuna başla"));
+}
+
+#[test]
+fn pause_overlay_covers_the_win_overlay() {
+    let board = Board::from_config(&GameConfig { seed: Some(3), ..GameConfig::new(4) });
+    let hud = Hud { score: 0, best_score: 0, win_pending: true, paused: true };
+    let mut surface = Recorder::default();
+    surface.render(&board, &hud);
+
+    let texts: Vec<&str> = surface.texts.iter().map(|(t, _, _)| t.as_str()).collect();
+    assert!(texts.contains(&"Duraklatıldı"));
+    assert!(!texts.contains(&"Kazandın!"));
+}
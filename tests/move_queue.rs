@@ -0,0 +1,32 @@
+use game_2048::{Direction, MoveQueue};
+
+#[test]
+fn queue_plays_moves_in_order() {
+    let mut queue = MoveQueue::new(3);
+    assert!(queue.push(Direction::Left));
+    assert!(queue.push(Direction::Up));
+    assert!(queue.push(Direction::Left));
+    assert_eq!(queue.len(), 3);
+    assert_eq!(queue.pop(), Some(Direction::Left));
+    assert_eq!(queue.pop(), Some(Direction::Up));
+    assert_eq!(queue.pop(), Some(Direction::Left));
+    assert_eq!(queue.pop(), None);
+}
+
+#[test]
+fn full_queue_drops_new_moves() {
+    let mut queue = MoveQueue::new(2);
+    assert!(queue.push(Direction::Down));
+    assert!(queue.push(Direction::Right));
+    assert!(!queue.push(Direction::Up));
+    assert_eq!(queue.pop(), Some(Direction::Down));
+    assert!(queue.push(Direction::Up));
+
+    queue.set_capacity(1);
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue.pop(), Some(Direction::Right));
+
+    let mut off = MoveQueue::new(0);
+    assert!(!off.push(Direction::Left));
+    assert!(off.is_empty());
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{random_board, DIRECTIONS};
+use game_2048::{BitBoard, Board, GameConfig, GameService, NTupleNetwork, Strategy, Tile};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn trained(episodes: u64) -> NTupleNetwork {
+    let mut network = NTupleNetwork::default();
+    for seed in 0..episodes {
+        let board = Board::from_config(&GameConfig { seed: Some(seed), ..GameConfig::new(4) });
+        network.train_episode(board, 0.1).unwrap();
+    }
+    network
+}
+
+fn transpose(board: BitBoard) -> BitBoard {
+    let mut out = BitBoard::default();
+    for row in 0..4 {
+        for col in 0..4 {
+            out.set(col, row, board.get(row, col));
+        }
+    }
+    out
+}
+
+#[test]
+fn symmetric_boards_evaluate_the_same() {
+    let network = trained(20);
+    let mut rng = StdRng::seed_from_u64(14);
+    for _ in 0..200 {
+        let board = BitBoard::try_from(&random_board(&mut rng, 4, 4)).unwrap();
+        let a = network.evaluate(board);
+        let b = network.evaluate(transpose(board));
+        assert!((a - b).abs() < 1e-6 * a.abs().max(1.0), "{} vs {}", a, b);
+    }
+}
+
+#[test]
+fn weights_roundtrip_through_bytes() {
+    let network = trained(10);
+    let restored = NTupleNetwork::from_bytes(&network.to_bytes()).unwrap();
+    assert_eq!(restored.tuples(), network.tuples());
+    assert_eq!(restored.to_bytes(), network.to_bytes());
+}
+
+#[test]
+fn rejects_bad_weights_and_shapes() {
+    let bytes = NTupleNetwork::default().to_bytes();
+    assert!(NTupleNetwork::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(NTupleNetwork::from_bytes(b"nope").is_err());
+    let mut extra = bytes.clone();
+    extra.push(0);
+    assert!(NTupleNetwork::from_bytes(&extra).is_err());
+
+    // a few bytes declaring many six-cell tuples are refused before allocating
+    let mut huge = b"NTUP".to_vec();
+    for n in [1u32, 100] { huge.extend_from_slice(&n.to_le_bytes()); }
+    for _ in 0..100 {
+        for n in [6u32, 0, 1, 2, 3, 4, 5] { huge.extend_from_slice(&n.to_le_bytes()); }
+    }
+    assert!(NTupleNetwork::from_bytes(&huge).unwrap_err().contains("boyutu"));
+
+    let mut nan = bytes.clone();
+    let last = nan.len() - 4;
+    nan[last..].copy_from_slice(&f32::NAN.to_le_bytes());
+    assert!(NTupleNetwork::from_bytes(&nan).is_err());
+    assert!(NTupleNetwork::new(vec![vec![0, 16]]).is_err());
+    assert!(NTupleNetwork::new(vec![(0..7).collect()]).is_err());
+}
+
+#[test]
+fn chooses_legal_moves_on_4x4_only() {
+    let mut network = trained(10);
+    let mut rng = StdRng::seed_from_u64(15);
+    for _ in 0..200 {
+        let board = random_board(&mut rng, 4, 4);
+        match network.choose(&board) {
+            Some((direction, _)) => assert!(board.clone().shift(direction).moved),
+            None => assert!(DIRECTIONS.iter().all(|&d| !board.clone().shift(d).moved)),
+        }
+    }
+    assert_eq!(network.choose(&random_board(&mut rng, 3, 5)), None);
+}
+
+#[test]
+fn tile_past_the_bitboard_ends_the_episode() {
+    let mut board = Board::from_config(&GameConfig { seed: Some(4), ..GameConfig::new(4) });
+    board.set(3, 3, Some(Tile::new(1 << 16)));
+    let mut network = NTupleNetwork::default();
+    let end = network.train_episode(board.clone(), 0.1).unwrap();
+    assert_eq!(end.cells, board.cells);
+
+    let wide = Board::from_config(&GameConfig { seed: Some(4), ..GameConfig::rect(3, 5) });
+    assert!(network.train_episode(wide, 0.1).is_err());
+}
+
+#[test]
+fn only_plays_4x4_games() {
+    let network = NTupleNetwork::default();
+    assert!(network.supports(4, 4).is_ok());
+    assert!(network.supports(5, 5).is_err());
+
+    let mut wide = GameService::with_config(GameConfig { seed: Some(5), ..GameConfig::rect(3, 5) });
+    assert!(wide.set_strategy(Some(Box::new(network.clone()))).is_err());
+    assert_eq!(wide.strategy_name(), "expectimax");
+
+    let mut game = GameService::with_seed(4, 5);
+    game.set_strategy(Some(Box::new(network))).unwrap();
+    assert_eq!(game.strategy_name(), "ntuple");
+    game.restart_with(GameConfig::new(5));
+    assert_eq!(game.strategy_name(), "expectimax");
+    assert!(game.best_move().is_some());
+}
@@ -0,0 +1,190 @@
+use game_2048::{
+    Direction, GameConfig, GameService, GameSnapshot, InputAction, Replay, ReplayAction, ReplayLog, ReplayPlayer, ReplayStep, Tile,
+    MAX_REPLAY_STEPS,
+};
+
+const DIRECTIONS: [Direction; 4] = [Direction::Left, Direction::Down, Direction::Right, Direction::Up];
+
+/// A seeded game with moves, an undo and a redo.
+fn played_game(config: GameConfig) -> GameService {
+    let mut game = GameService::with_config(config);
+    for direction in DIRECTIONS.iter().cycle().take(12) {
+        game.apply_move(*direction);
+    }
+    game.undo();
+    game.undo();
+    game.redo();
+    for direction in DIRECTIONS.iter().rev().cycle().take(6) {
+        game.apply_move(*direction);
+    }
+    game
+}
+
+#[test]
+fn replay_plays_out_to_the_same_game() {
+    for config in [GameConfig { seed: Some(20), ..GameConfig::new(4) }, GameConfig::rect(3, 5)] {
+        let game = played_game(config);
+        let json = game.replay().unwrap().to_json().unwrap();
+        let mut player = ReplayPlayer::from_json(&json).unwrap();
+
+        player.seek(usize::MAX);
+        assert!(player.at_end());
+        assert_eq!(player.frame().board.cells, game.board().cells);
+        assert_eq!(player.frame().board.score, game.score());
+        assert_eq!(player.frame().moves, game.moves());
+    }
+}
+
+#[test]
+fn replay_records_every_action_in_order() {
+    let replay = played_game(GameConfig { seed: Some(20), ..GameConfig::new(4) }).replay().unwrap();
+    let undos = replay.actions.iter().filter(|s| s.action == ReplayAction::Undo).count();
+    let redos = replay.actions.iter().filter(|s| s.action == ReplayAction::Redo).count();
+    assert_eq!((undos, redos), (2, 1));
+    assert!(replay.actions.windows(2).all(|w| w[0].t_ms <= w[1].t_ms));
+
+    let json = replay.to_json().unwrap();
+    assert!(json.contains("\"action\":\"undo\""));
+    assert!(json.contains("\"action\":\"left\""));
+}
+
+#[test]
+fn reset_starts_a_new_replay() {
+    let mut game = played_game(GameConfig { seed: Some(21), ..GameConfig::new(4) });
+    game.reset();
+    let replay = game.replay().unwrap();
+    assert!(replay.actions.is_empty());
+    let player = ReplayPlayer::new(replay).unwrap();
+    assert_eq!(player.frame().board.cells, game.board().cells);
+}
+
+#[test]
+fn restart_with_deals_the_same_game_for_the_same_seed() {
+    let config = GameConfig { seed: Some(23), ..GameConfig::rect(3, 5) };
+    let mut game = played_game(GameConfig { seed: Some(24), ..GameConfig::new(4) });
+    let best = game.best_score();
+    game.restart_with(config.clone());
+    let fresh = GameService::with_config(config);
+    assert_eq!(game.board().cells, fresh.board().cells);
+    assert_eq!((game.moves(), game.best_score()), (0, best));
+    assert!(!game.can_undo());
+
+    let replay = game.replay().unwrap();
+    assert!(replay.actions.is_empty());
+    assert_eq!(ReplayPlayer::new(replay).unwrap().frame().board.cells, fresh.board().cells);
+}
+
+#[test]
+fn keep_playing_is_replayed() {
+    let config = GameConfig { seed: Some(22), win_target: 8, ..GameConfig::new(4) };
+    let mut game = GameService::with_config(config);
+    let mut directions = DIRECTIONS.iter().cycle();
+    while !game.win_pending() {
+        game.apply_move(*directions.next().unwrap());
+    }
+    game.keep_playing();
+    for direction in directions.take(4) {
+        game.apply_move(*direction);
+    }
+
+    let mut player = ReplayPlayer::new(game.replay().unwrap()).unwrap();
+    player.seek(player.len());
+    assert_eq!(player.frame().board.cells, game.board().cells);
+}
+
+#[test]
+fn stepping_moves_through_the_frames() {
+    let game = played_game(GameConfig { seed: Some(23), ..GameConfig::new(4) });
+    let mut player = ReplayPlayer::new(game.replay().unwrap()).unwrap();
+    let start = player.frame().board.clone();
+
+    assert!(player.step_back().is_none());
+    let first = player.step_forward().unwrap().clone();
+    assert!(matches!(first.step.unwrap().action, ReplayAction::Move(_)));
+    assert!(first.outcome.moved);
+    assert_eq!(player.step_back().unwrap().board, start);
+
+    player.seek(player.len());
+    assert!(player.step_forward().is_none());
+    assert_eq!(player.position(), player.len());
+}
+
+#[test]
+fn tampered_replay_is_rejected() {
+    let replay = played_game(GameConfig { seed: Some(24), ..GameConfig::new(4) }).replay().unwrap();
+
+    let mut edited = replay.clone();
+    edited.final_score += 4;
+    let err = Replay::from_json(&edited.to_json().unwrap()).unwrap_err();
+    assert!(err.contains("saglama"), "{}", err);
+
+    // a consistent checksum over a result the moves don't reach
+    edited.checksum = edited.compute_checksum();
+    let err = ReplayPlayer::from_json(&edited.to_json().unwrap()).unwrap_err();
+    assert!(err.contains("sonucu tutmuyor"), "{}", err);
+
+    // nothing was undone, so there is nothing to redo
+    let mut edited = replay.clone();
+    let last = *edited.actions.last().unwrap();
+    edited.actions.push(ReplayStep { action: ReplayAction::Redo, ..last });
+    edited.checksum = edited.compute_checksum();
+    let err = ReplayPlayer::new(edited).unwrap_err();
+    assert!(err.contains("redo"), "{}", err);
+
+    assert!(Replay::from_json("{\"version\": 1}").is_err());
+}
+
+#[test]
+fn replay_survives_a_save() {
+    let game = played_game(GameConfig { seed: Some(25), ..GameConfig::new(4) });
+    let json = game.snapshot().to_json().unwrap();
+    let bytes = game.snapshot().to_bytes().unwrap();
+    for snapshot in [GameSnapshot::from_json(&json).unwrap(), GameSnapshot::from_bytes(&bytes).unwrap()] {
+        let mut restored = GameService::from_snapshot(snapshot);
+        assert_eq!(restored.replay().unwrap().actions, game.replay().unwrap().actions);
+
+        restored.apply_move(Direction::Down);
+        restored.apply_move(Direction::Left);
+        let mut player = ReplayPlayer::new(restored.replay().unwrap()).unwrap();
+        player.seek(player.len());
+        assert_eq!(player.frame().board.cells, restored.board().cells);
+    }
+}
+
+#[test]
+fn edited_board_has_no_valid_replay() {
+    // tiles placed by hand are not in the replay, so it stops matching
+    let mut game = GameService::with_config(GameConfig { seed: Some(26), ..GameConfig::new(4) });
+    let mut snapshot = game.snapshot();
+    snapshot.board.set(0, 0, Some(Tile::new(1024)));
+    game = GameService::from_snapshot(snapshot);
+    game.apply_move(Direction::Right);
+    assert!(ReplayPlayer::new(game.replay().unwrap()).is_err());
+}
+
+#[test]
+fn replay_log_is_capped() {
+    let mut log = ReplayLog::new(1, 0.0);
+    for i in 0..MAX_REPLAY_STEPS {
+        assert!(log.push(ReplayAction::Move(Direction::Left), i as f64));
+    }
+    assert!(!log.push(ReplayAction::Undo, 1e9));
+    assert_eq!(log.steps.len(), MAX_REPLAY_STEPS);
+
+    // a longer file is refused before it is simulated
+    let mut replay = played_game(GameConfig { seed: Some(27), ..GameConfig::new(4) }).replay().unwrap();
+    let last = *replay.actions.last().unwrap();
+    replay.actions.resize(MAX_REPLAY_STEPS + 1, last);
+    replay.checksum = replay.compute_checksum();
+    assert!(ReplayPlayer::new(replay).unwrap_err().contains("uzun"));
+}
+
+#[test]
+fn actions_share_names_with_input_actions() {
+    for action in ReplayAction::ALL {
+        assert_eq!(action.as_str().parse::<ReplayAction>(), Ok(action));
+        assert!(action.as_str().parse::<InputAction>().is_ok(), "{}", action.as_str());
+    }
+    assert_eq!(InputAction::Continue.as_str(), ReplayAction::KeepPlaying.as_str());
+    assert!("restart".parse::<ReplayAction>().is_err());
+}
@@ -0,0 +1,43 @@
+use game_2048::{Board, Direction, GameService};
+
+const MOVES: [Direction; 4] = [Direction::Left, Direction::Down, Direction::Right, Direction::Up];
+
+/// Scores and cells after each move of a seeded game.
+fn play(seed: u64) -> Vec<(u32, Vec<Option<u32>>)> {
+    let mut game = GameService::with_seed(4, seed);
+    let mut boards = Vec::new();
+    for direction in MOVES.iter().cycle().take(60) {
+        game.slide(*direction);
+        boards.push((game.score(), game.board().cells.iter().map(|c| c.as_ref().map(|t| t.value)).collect()));
+    }
+    boards
+}
+
+#[test]
+fn same_seed_spawns_the_same_tiles() {
+    for seed in [0, 1, 42, u64::MAX] {
+        let (mut a, mut b) = (Board::with_seed(4, seed), Board::with_seed(4, seed));
+        assert_eq!(a.cells, b.cells);
+        for _ in 0..10 {
+            assert_eq!(a.spawn_random_tile(), b.spawn_random_tile());
+        }
+        assert_eq!(a.cells, b.cells);
+    }
+}
+
+#[test]
+fn same_seed_plays_the_same_game() {
+    assert_eq!(play(7), play(7));
+    assert_ne!(play(7), play(8), "different seeds should deal different games");
+}
+
+#[test]
+fn reset_keeps_drawing_from_the_seeded_stream() {
+    let (mut a, mut b) = (GameService::with_seed(4, 9), GameService::with_seed(4, 9));
+    a.slide(Direction::Left);
+    b.slide(Direction::Left);
+    a.reset();
+    b.reset();
+    assert_eq!(a.board().cells, b.board().cells);
+    assert_ne!(a.board().rng, GameService::with_seed(4, 9).board().rng);
+}
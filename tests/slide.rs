@@ -0,0 +1,164 @@
+mod common;
+
+use common::{random_board, DIRECTIONS};
+use game_2048::{Board, Direction, Tile};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Cell indices of every line in the order tiles slide along it, the
+/// destination first.
+fn lines(rows: usize, cols: usize, direction: Direction) -> Vec<Vec<usize>> {
+    match direction {
+        Direction::Left => (0..rows).map(|r| (0..cols).map(|c| r * cols + c).collect()).collect(),
+        Direction::Right => (0..rows).map(|r| (0..cols).rev().map(|c| r * cols + c).collect()).collect(),
+        Direction::Up => (0..cols).map(|c| (0..rows).map(|r| r * cols + c).collect()).collect(),
+        Direction::Down => (0..cols).map(|c| (0..rows).rev().map(|r| r * cols + c).collect()).collect(),
+    }
+}
+
+/// The textbook slide, written independently of `Board`: drop the gaps,
+/// merge equal neighbours once from the front, pad with empty cells.
+fn reference_slide(cells: &[u32], rows: usize, cols: usize, direction: Direction) -> (Vec<u32>, u32) {
+    let mut out = cells.to_vec();
+    let mut score = 0;
+    for line in lines(rows, cols, direction) {
+        let tiles: Vec<u32> = line.iter().map(|&i| cells[i]).filter(|&v| v != 0).collect();
+        let mut merged = Vec::new();
+        let mut i = 0;
+        while i < tiles.len() {
+            if i + 1 < tiles.len() && tiles[i] == tiles[i + 1] {
+                merged.push(tiles[i] * 2);
+                score += tiles[i] * 2;
+                i += 2;
+            } else {
+                merged.push(tiles[i]);
+                i += 1;
+            }
+        }
+        merged.resize(line.len(), 0);
+        for (&cell, value) in line.iter().zip(merged) {
+            out[cell] = value;
+        }
+    }
+    (out, score)
+}
+
+fn values(board: &Board) -> Vec<u32> {
+    board.cells.iter().map(|c| c.as_ref().map_or(0, |t| t.value)).collect()
+}
+
+#[test]
+fn lines_slide_as_written_by_hand() {
+    let cases: [([u32; 4], Direction, [u32; 4], u32); 7] = [
+        ([2, 2, 2, 2], Direction::Left, [4, 4, 0, 0], 8),
+        ([2, 2, 2, 2], Direction::Right, [0, 0, 4, 4], 8),
+        ([4, 0, 4, 8], Direction::Left, [8, 8, 0, 0], 8),
+        ([2, 2, 4, 0], Direction::Right, [0, 0, 4, 4], 4),
+        ([4, 4, 8, 8], Direction::Left, [8, 16, 0, 0], 24),
+        ([2, 4, 8, 16], Direction::Left, [2, 4, 8, 16], 0),
+        ([0, 0, 0, 2], Direction::Left, [2, 0, 0, 0], 0),
+    ];
+    for (line, direction, expected, score) in cases {
+        let mut board = Board::new(4);
+        for (col, &value) in line.iter().enumerate() {
+            board.set(1, col, (value != 0).then(|| Tile::new(value)));
+        }
+        for row in [0, 2, 3] {
+            for col in 0..4 { board.set(row, col, None); }
+        }
+        let outcome = board.shift(direction);
+        let row: Vec<u32> = values(&board)[4..8].to_vec();
+        assert_eq!(row, expected, "{:?} {:?}", line, direction);
+        assert_eq!(outcome.score_gained, score, "{:?} {:?}", line, direction);
+        assert_eq!(outcome.moved, line != expected);
+    }
+
+    // the same rules down a column
+    let mut board = Board::new(4);
+    board.cells = vec![None; 16];
+    for (row, value) in [(0, 2), (1, 2), (3, 4)] { board.set(row, 2, Some(Tile::new(value))); }
+    board.shift(Direction::Down);
+    assert_eq!(values(&board), vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 4, 0]);
+}
+
+#[test]
+fn apply_move_matches_a_reference_slide() {
+    let mut rng = StdRng::seed_from_u64(6);
+    for (rows, cols) in [(2, 2), (3, 3), (4, 4), (5, 5), (6, 6), (3, 5), (5, 3), (4, 6), (2, 8), (1, 4)] {
+        for _ in 0..2_000 {
+            let board = random_board(&mut rng, rows, cols);
+            let before = values(&board);
+            for direction in DIRECTIONS {
+                let (expected, score) = reference_slide(&before, rows, cols, direction);
+                let moved = expected != before;
+
+                let mut applied = board.clone();
+                let outcome = applied.apply_move(direction);
+                assert_eq!(outcome.moved, moved, "{:?} on {:?}", direction, before);
+                assert_eq!(outcome.score_gained, score, "{:?} on {:?}", direction, before);
+                assert_eq!(applied.score, board.score + score);
+
+                // the slid board plus spawns on cells it left empty, and nothing when nothing moved
+                let after = values(&applied);
+                let spawned: Vec<usize> = (0..after.len()).filter(|&i| after[i] != expected[i]).collect();
+                assert!(spawned.iter().all(|&i| expected[i] == 0), "{:?} on {:?}", direction, before);
+                let empty = expected.iter().filter(|&&v| v == 0).count();
+                let count = if moved { board.spawn.per_move.min(empty) } else { 0 };
+                assert_eq!(spawned.len(), count, "{:?} on {:?}", direction, before);
+                assert_eq!(outcome.spawned.len(), count);
+
+                let won = |cells: &[u32]| cells.iter().any(|&v| v >= board.win_target);
+                assert_eq!(outcome.won, won(&after) && !won(&before));
+                // over means full with no merge left
+                let stuck = !after.contains(&0) && DIRECTIONS.iter().all(|&d| reference_slide(&after, rows, cols, d).0 == after);
+                assert_eq!(outcome.over, stuck, "{:?} on {:?}", direction, before);
+            }
+        }
+    }
+}
+
+#[test]
+fn move_events_describe_the_shifted_board() {
+    let mut rng = StdRng::seed_from_u64(66);
+    for _ in 0..5_000 {
+        let board = random_board(&mut rng, 4, 4);
+        for direction in DIRECTIONS {
+            let mut shifted = board.clone();
+            let outcome = shifted.shift(direction);
+
+            let mut replayed = board.clone();
+            replayed.cells = vec![None; board.cells.len()];
+            for e in &outcome.events {
+                assert_eq!(board.get(e.from_row, e.from_col).as_ref().map(|t| t.value), Some(e.value));
+                replayed.set(e.to_row, e.to_col, Some(Tile::new(e.merged_into_value.unwrap_or(e.value))));
+            }
+            replayed.score = shifted.score;
+            assert_eq!(replayed, shifted, "{:?} on {:?}", direction, board.cells);
+
+            let changed = outcome.events.iter()
+                .any(|e| e.merged_into_value.is_some() || (e.from_row, e.from_col) != (e.to_row, e.to_col));
+            assert_eq!(outcome.moved, changed);
+        }
+    }
+}
+
+#[test]
+fn no_op_move_leaves_board_untouched() {
+    let mut rng = StdRng::seed_from_u64(60);
+    let mut no_ops = 0;
+    for _ in 0..5_000 {
+        let board = random_board(&mut rng, 4, 4);
+        for direction in DIRECTIONS {
+            let mut after = board.clone();
+            let outcome = after.apply_move(direction);
+            if !outcome.moved {
+                no_ops += 1;
+                // same tiles, same score and no RNG draw for a spawn
+                assert_eq!(after, board, "{:?} on {:?}", direction, board.cells);
+                assert!(outcome.spawned.is_empty());
+                assert_eq!(outcome.score_gained, 0);
+            }
+        }
+    }
+    assert!(no_ops > 0);
+}
@@ -0,0 +1,133 @@
+use game_2048::{Direction, GameService, GameSnapshot, SpawnPolicy, Tile, MAX_TILE};
+
+const V1: &str = r#"{
+    "version": 1,
+    "board": { "size": 2, "cells": [{ "value": 2, "merged": false }, null, null, { "value": 4, "merged": false }], "score": 4, "rng": { "state": 7 } },
+    "moves": 1,
+    "history": {
+        "limit": 64,
+        "undo": [{ "size": 2, "cells": [null, { "value": 2, "merged": false }, null, { "value": 2, "merged": false }], "score": 0, "rng": { "state": 6 } }],
+        "redo": []
+    }
+}"#;
+
+const V2: &str = r#"{
+    "version": 2,
+    "config": { "size": 3, "win_target": 512 },
+    "board": { "size": 3, "cells": [null, null, null, null, { "value": 8, "merged": false }, null, null, null, { "value": 2, "merged": false }], "score": 8, "rng": { "state": 1 }, "win_target": 512 },
+    "moves": 3,
+    "history": { "limit": null, "undo": [], "redo": [] },
+    "keep_playing": true
+}"#;
+
+const V3: &str = r#"{
+    "version": 3,
+    "config": { "size": 2, "seed": 9, "spawn": { "values": [[2, 1]], "per_move": 2, "initial": 1, "policy": "CornerBiased" } },
+    "board": { "size": 2, "cells": [null, null, null, { "value": 2, "merged": false }], "score": 0, "rng": { "state": 9 },
+               "spawn": { "values": [[2, 1]], "per_move": 2, "initial": 1, "policy": "CornerBiased" } },
+    "moves": 0,
+    "history": { "limit": 8, "undo": [], "redo": [] }
+}"#;
+
+const V4: &str = r#"{
+    "version": 4,
+    "config": { "rows": 2, "cols": 3 },
+    "board": { "rows": 2, "cols": 3, "cells": [{ "value": 2, "merged": false }, null, null, null, null, { "value": 2, "merged": false }], "score": 0, "rng": { "state": 3 } },
+    "moves": 0,
+    "history": { "limit": 64, "undo": [], "redo": [] }
+}"#;
+
+fn values(snapshot: &GameSnapshot) -> Vec<u32> {
+    snapshot.board.cells.iter().map(|c| c.as_ref().map_or(0, |t| t.value)).collect()
+}
+
+#[test]
+fn version_1_is_upgraded() {
+    let snapshot = GameSnapshot::from_json(V1).unwrap();
+    assert_eq!(snapshot.version, 5);
+    assert_eq!((snapshot.config.rows, snapshot.config.cols, snapshot.config.win_target), (2, 2, 2048));
+    assert_eq!(values(&snapshot), vec![2, 0, 0, 4]);
+    assert!(snapshot.replay.is_none());
+
+    let mut game = GameService::from_snapshot(snapshot);
+    assert!(game.undo());
+    assert_eq!(game.board().rng.state(), 6);
+    assert_eq!((game.board().rows, game.board().cols, game.score()), (2, 2, 0));
+}
+
+#[test]
+fn version_1_has_no_binary_form() {
+    let mut bytes = b"2048".to_vec();
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&[0x80]);
+    assert!(GameSnapshot::from_bytes(&bytes).unwrap_err().contains("desteklenmiyor"));
+}
+
+#[test]
+fn versions_2_to_4_are_upgraded() {
+    let v2 = GameSnapshot::from_json(V2).unwrap();
+    assert_eq!((v2.config.rows, v2.config.cols, v2.config.win_target), (3, 3, 512));
+    assert!(v2.keep_playing);
+    assert_eq!((v2.board.score, v2.moves), (8, 3));
+
+    let v3 = GameSnapshot::from_json(V3).unwrap();
+    assert_eq!(v3.config.seed, Some(9));
+    assert_eq!(v3.config.spawn.policy, SpawnPolicy::CornerBiased);
+    assert_eq!(v3.board.spawn.per_move, 2);
+    assert_eq!(v3.history.limit(), Some(8));
+
+    let v4 = GameSnapshot::from_json(V4).unwrap();
+    assert_eq!((v4.board.rows, v4.board.cols), (2, 3));
+    assert_eq!(values(&v4), vec![2, 0, 0, 0, 0, 2]);
+
+    for snapshot in [v2, v3, v4] {
+        assert_eq!(snapshot.version, 5);
+        let json = snapshot.to_json().unwrap();
+        assert_eq!(values(&GameSnapshot::from_json(&json).unwrap()), values(&snapshot));
+    }
+}
+
+#[test]
+fn malformed_boards_are_rejected() {
+    // a history board with too few cells would panic on undo
+    let short_history = V1.replace(r#"[null, { "value": 2, "merged": false }, null,"#, r#"[null,"#);
+    assert!(GameSnapshot::from_json(&short_history).unwrap_err().contains("tutarsiz"));
+
+    let game = GameService::with_seed(4, 1);
+    let mut snapshot = game.snapshot();
+    snapshot.config.rows = 5;
+    assert!(GameSnapshot::from_json(&snapshot.to_json().unwrap()).is_err());
+
+    let mut snapshot = game.snapshot();
+    snapshot.board.cells.pop();
+    assert!(GameSnapshot::from_json(&snapshot.to_json().unwrap()).is_err());
+
+    let mut snapshot = game.snapshot();
+    snapshot.config.win_target = 4096;
+    assert!(GameSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap_err().contains("kurallari"));
+
+    for value in [3, 1, 0] {
+        let mut snapshot = game.snapshot();
+        snapshot.board.set(1, 1, Some(Tile::new(value)));
+        assert!(GameSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap_err().contains("tas"), "{} accepted", value);
+    }
+
+    let mut snapshot = game.snapshot();
+    snapshot.config.win_target = 0;
+    assert!(GameSnapshot::from_bytes(&snapshot.to_bytes().unwrap()).unwrap_err().contains("ayarlar"));
+}
+
+#[test]
+fn largest_tiles_load_and_do_not_overflow() {
+    let mut game = GameService::with_seed(4, 2);
+    let mut snapshot = game.snapshot();
+    for col in 0..4 { snapshot.board.set(0, col, Some(Tile::new(if col < 2 { MAX_TILE } else { MAX_TILE / 2 }))); }
+    snapshot.board.score = u32::MAX - 1;
+    game = GameService::from_snapshot(GameSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap());
+
+    game.keep_playing();
+    game.apply_move(Direction::Left);
+    let row: Vec<u32> = (0..4).map(|c| game.board().get(0, c).as_ref().map_or(0, |t| t.value)).collect();
+    assert_eq!(row, vec![MAX_TILE, MAX_TILE, MAX_TILE, 0]);
+    assert_eq!(game.score(), u32::MAX);
+}
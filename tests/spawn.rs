@@ -0,0 +1,90 @@
+use game_2048::{Board, Direction, GameConfig, GameRng, SpawnPolicy, SpawnRules, MAX_SPAWN_VALUE};
+
+fn config(spawn: SpawnRules) -> GameConfig {
+    GameConfig { seed: Some(3), spawn, ..GameConfig::new(4) }
+}
+
+#[test]
+fn validate_rejects_bad_tables() {
+    assert!(SpawnRules::default().validate().is_ok());
+    // weights are summed without overflowing
+    let huge = SpawnRules { values: vec![(2, u32::MAX), (4, u32::MAX)], ..SpawnRules::default() };
+    assert!(huge.validate().is_ok());
+
+    for values in [vec![], vec![(2, 0), (4, 0)], vec![(3, 1)], vec![(1, 1)], vec![(MAX_SPAWN_VALUE * 2, 1)], vec![(1 << 31, 1)]] {
+        let rules = SpawnRules { values: values.clone(), ..SpawnRules::default() };
+        assert!(rules.validate().is_err(), "{:?} accepted", values);
+    }
+    assert!(SpawnRules { initial: 0, ..SpawnRules::default() }.validate().is_err());
+
+    assert!(config(SpawnRules { per_move: 16, initial: 16, ..SpawnRules::default() }).validate().is_ok());
+    assert!(config(SpawnRules { per_move: 17, ..SpawnRules::default() }).validate().is_err());
+    assert!(config(SpawnRules { initial: 17, ..SpawnRules::default() }).validate().is_err());
+    // a target that can spawn would be won at once
+    let eights = SpawnRules { values: vec![(8, 1)], ..SpawnRules::default() };
+    assert!(GameConfig { win_target: 8, ..config(eights) }.validate().is_err());
+}
+
+#[test]
+fn values_follow_their_weights() {
+    let rules = SpawnRules { values: vec![(2, 1), (4, 0), (8, 3)], ..SpawnRules::default() };
+    let probabilities: Vec<(u32, f64)> = rules.probabilities().collect();
+    assert_eq!(probabilities, vec![(2, 0.25), (8, 0.75)]);
+    assert_eq!(rules.max_value(), 8);
+
+    let mut rng = GameRng::new(11);
+    let draws = 20_000;
+    let eights = (0..draws).map(|_| rules.pick_value(&mut rng)).inspect(|v| assert_ne!(*v, 4)).filter(|v| *v == 8).count();
+    let share = eights as f64 / draws as f64;
+    assert!((share - 0.75).abs() < 0.02, "8 spawned {:.3} of the time", share);
+
+    let huge = SpawnRules { values: vec![(2, u32::MAX), (4, u32::MAX)], ..SpawnRules::default() };
+    let fours = (0..1_000).filter(|_| huge.pick_value(&mut rng) == 4).count();
+    assert!((400..600).contains(&fours), "{} fours", fours);
+}
+
+#[test]
+fn initial_and_per_move_control_how_many_tiles_appear() {
+    let board = Board::from_config(&config(SpawnRules { initial: 5, ..SpawnRules::default() }));
+    assert_eq!(board.cells.iter().flatten().count(), 5);
+
+    let mut board = Board::from_config(&config(SpawnRules { initial: 1, per_move: 3, ..SpawnRules::default() }));
+    let outcome = [Direction::Left, Direction::Right, Direction::Up, Direction::Down].iter()
+        .map(|d| board.apply_move(*d))
+        .find(|o| o.moved)
+        .unwrap();
+    assert_eq!(outcome.spawned.len(), 3);
+    assert_eq!(board.cells.iter().flatten().count(), 4);
+
+    let mut board = Board::from_config(&config(SpawnRules { per_move: 0, ..SpawnRules::default() }));
+    let before = board.cells.iter().flatten().count();
+    for direction in [Direction::Left, Direction::Right, Direction::Up, Direction::Down] {
+        assert!(board.apply_move(direction).spawned.is_empty());
+    }
+    assert!(board.cells.iter().flatten().count() <= before);
+}
+
+#[test]
+fn corner_biased_prefers_corners() {
+    let rules = SpawnRules { policy: SpawnPolicy::CornerBiased, ..SpawnRules::default() };
+    let empties: Vec<(usize, usize)> = (0..5).flat_map(|r| (0..5).map(move |c| (r, c))).collect();
+    let mut rng = GameRng::new(12);
+    let mut counts = [[0u32; 5]; 5];
+    for _ in 0..50_000 {
+        let (row, col) = rules.pick_position(&empties, 5, 5, &mut rng);
+        counts[row][col] += 1;
+    }
+    for corner in [counts[0][0], counts[0][4], counts[4][0], counts[4][4]] {
+        assert!(corner > 3 * counts[2][2], "corner {} vs centre {}", corner, counts[2][2]);
+    }
+
+    let chances = rules.position_probabilities(&empties, 5, 5);
+    assert!((chances.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!((chances[0] / chances[12] - 5.0).abs() < 1e-9, "corner weighs 5, centre 1");
+
+    // only empty cells are ever picked
+    let empties = [(1, 1), (3, 2)];
+    for _ in 0..100 {
+        assert!(empties.contains(&rules.pick_position(&empties, 5, 5, &mut rng)));
+    }
+}
@@ -0,0 +1,38 @@
+use game_2048::{Direction, GameConfig, GameService};
+
+fn game() -> GameService {
+    GameService::with_config(GameConfig { seed: Some(18), ..GameConfig::new(4) })
+}
+
+#[test]
+fn board_svg_shows_every_tile() {
+    let game = game();
+    let svg = game.to_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"420\" height=\"520\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    let tiles: Vec<u32> = game.board().cells.iter().flatten().map(|t| t.value).collect();
+    // background, 16 empty cells, one rect per tile
+    assert_eq!(svg.matches("<rect").count(), 1 + 16 + tiles.len());
+    for value in tiles {
+        assert!(svg.contains(&format!(">{}</text>", value)));
+    }
+    assert!(svg.contains(">Skor: 0</text>"));
+}
+
+#[test]
+fn frames_run_from_the_old_board_to_the_new_one() {
+    let mut game = game();
+    let outcome = [Direction::Left, Direction::Right, Direction::Up, Direction::Down]
+        .into_iter()
+        .map(|d| game.apply_move(d))
+        .find(|o| o.moved)
+        .unwrap();
+
+    let start = game.frame_svg(&outcome, 0.0);
+    let end = game.frame_svg(&outcome, 1.0);
+    assert_ne!(start, end);
+    // spawned tiles only appear at the end of the animation
+    let rects = |svg: &str| svg.matches("<rect").count();
+    assert!(rects(&end) >= rects(&start) + outcome.spawned.len());
+}